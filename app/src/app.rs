@@ -4,11 +4,11 @@ use crate::ui::{AppContext, Ui};
 use pss_core::math::ema::EMA;
 use pss_core::math::point::Point;
 use pss_core::math::size::Size;
-use pss_core::simulation::source::local::LocalSim;
 use pss_core::simulation::source::SimSource;
-use pss_core::simulation::state::SimState;
 use pss_core::simulation::sync::command::SimCommand;
+use pss_core::simulation::sync::event::SimEvent;
 use pss_core::simulation::sync::snapshot::SimSnapshot;
+use std::collections::VecDeque;
 use std::sync::Arc;
 use std::time::Instant;
 use winit::application::ApplicationHandler;
@@ -16,6 +16,8 @@ use winit::event::{ElementState, MouseButton, MouseScrollDelta, WindowEvent};
 use winit::event_loop::ActiveEventLoop;
 use winit::window::{Window, WindowId};
 
+const RECENT_EVENTS_CAPACITY: usize = 20;
+
 pub struct App {
     window: Option<Arc<Window>>,
     gfx: Option<Gfx>,
@@ -23,6 +25,7 @@ pub struct App {
     ui: Ui,
     simulation: Option<Box<dyn SimSource>>,
     sim_snapshot: Option<SimSnapshot>,
+    recent_events: VecDeque<SimEvent>,
     cursor_pos: Point<f32>,
     drag_start: Option<Point<f32>>,
     avg_gfx_secs: EMA,
@@ -30,16 +33,15 @@ pub struct App {
 }
 
 impl App {
-    pub fn new() -> Self {
-        let sim = LocalSim::spawn(SimState::new_with_seed(2));
-
+    pub fn new(simulation: Box<dyn SimSource>) -> Self {
         Self {
             window: None,
             gfx: None,
             camera: Camera::new(),
             ui: Ui::default(),
-            simulation: Some(Box::new(sim)),
+            simulation: Some(simulation),
             sim_snapshot: None,
+            recent_events: VecDeque::new(),
             cursor_pos: Point::default(),
             drag_start: None,
             avg_gfx_secs: EMA::default(),
@@ -53,7 +55,15 @@ impl App {
 
         if let Some(sim) = &self.simulation {
             let rect = self.camera.visible_rect(screen_size);
+            sim.send_command(SimCommand::SetScreenSize(screen_size));
             sim.send_command(SimCommand::SetVisibleRect(rect));
+
+            while let Some(event) = sim.poll_event() {
+                if self.recent_events.len() >= RECENT_EVENTS_CAPACITY {
+                    self.recent_events.pop_front();
+                }
+                self.recent_events.push_back(event);
+            }
         }
 
         let ui_start = Instant::now();
@@ -61,6 +71,7 @@ impl App {
             let app_ctx = AppContext {
                 simulation: self.simulation.as_deref(),
                 sim_snapshot: self.sim_snapshot.as_ref(),
+                recent_events: &self.recent_events,
                 camera: &self.camera,
                 cursor_screen_pos: self.cursor_pos,
                 screen_size,
@@ -80,16 +91,17 @@ impl App {
 
             gfx.resize_cell_buffer(frame_size.width, frame_size.height);
 
-            let cells_wide = frame_size.width as f32;
-            let cells_high = frame_size.height as f32;
+            // Each texel covers `cell_size` world cells when zoomed out.
+            let cell_size = frame.cell_size() as f32;
+            let texels_wide = frame_size.width as f32 * cell_size;
+            let texels_high = frame_size.height as f32 * cell_size;
 
-            let fract_x = frame_rect.min.x.rem_euclid(1.0);
-            let fract_y = frame_rect.min.y.rem_euclid(1.0);
+            let offset = frame_rect.min - frame.origin();
 
-            let uv_offset = [fract_x / cells_wide, fract_y / cells_high];
+            let uv_offset = [offset.x / texels_wide, offset.y / texels_high];
             let uv_scale = [
-                frame_rect.width() / cells_wide,
-                frame_rect.height() / cells_high,
+                frame_rect.width() / texels_wide,
+                frame_rect.height() / texels_high,
             ];
 
             gfx.set_camera(uv_offset, uv_scale);
@@ -2,6 +2,10 @@ use pss_core::math::point::Point;
 use pss_core::math::rect::Rect;
 use pss_core::math::size::Size;
 
+/// Screen pixels per world cell, below 1 the simulation renders a level-of-detail frame.
+const MIN_ZOOM: f32 = 1.0 / 4096.0;
+const MAX_ZOOM: f32 = 10000.0;
+
 pub struct Camera {
     pub center: Point<f32>,
     pub zoom: f32,
@@ -38,7 +42,7 @@ impl Camera {
 
     pub fn zoom_at(&mut self, screen_pos: Point<f32>, factor: f32, screen: Size<u32>) {
         let world_before = self.screen_to_world(screen_pos, screen);
-        self.zoom = (self.zoom * factor).clamp(1.0, 10000.0);
+        self.zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
         let world_after = self.screen_to_world(screen_pos, screen);
         self.center = self.center + (world_before - world_after);
     }
@@ -1,3 +1,9 @@
+use pss_core::math::point::Point;
+use pss_core::simulation::source::local::LocalSim;
+use pss_core::simulation::source::replay::ReplaySim;
+use pss_core::simulation::source::SimSource;
+use pss_core::simulation::state::resource::ResourceRegistry;
+use pss_core::simulation::state::SimState;
 use std::error::Error;
 use winit::event_loop::{ControlFlow, EventLoop};
 
@@ -12,8 +18,27 @@ fn main() -> Result<(), Box<dyn Error>> {
     let event_loop = EventLoop::new()?;
     event_loop.set_control_flow(ControlFlow::Poll);
 
-    let mut app = app::App::new();
+    let simulation: Box<dyn SimSource> = match arg_value("--replay") {
+        Some(path) => Box::new(ReplaySim::load(path)?),
+        None => {
+            let resource_types = match arg_value("--resources") {
+                Some(path) => ResourceRegistry::load_from(path)?,
+                None => ResourceRegistry::default(),
+            };
+            Box::new(LocalSim::spawn(
+                SimState::new_with_seed(2)
+                    .with_resource_types(resource_types)
+                    .with_colony(Point::new(1000, 700)),
+            ))
+        }
+    };
+
+    let mut app = app::App::new(simulation);
 
     event_loop.run_app(&mut app)?;
     Ok(())
 }
+
+fn arg_value(name: &str) -> Option<String> {
+    std::env::args().skip_while(|arg| arg != name).nth(1)
+}
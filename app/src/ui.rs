@@ -5,7 +5,9 @@ use pss_core::math::ema::EMA;
 use pss_core::math::point::Point;
 use pss_core::math::size::Size;
 use pss_core::simulation::source::SimSource;
+use pss_core::simulation::sync::event::SimEvent;
 use pss_core::simulation::sync::snapshot::SimSnapshot;
+use std::collections::VecDeque;
 use winit::event::{ElementState, KeyEvent, MouseButton};
 use winit::keyboard::{KeyCode, PhysicalKey};
 
@@ -15,6 +17,7 @@ mod windows;
 pub struct AppContext<'a> {
     pub simulation: Option<&'a dyn SimSource>,
     pub sim_snapshot: Option<&'a SimSnapshot>,
+    pub recent_events: &'a VecDeque<SimEvent>,
     pub camera: &'a Camera,
     pub cursor_screen_pos: Point<f32>,
     pub screen_size: Size<u32>,
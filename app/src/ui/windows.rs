@@ -4,6 +4,7 @@ use egui::{Context, Id, Ui, Widget, WidgetText};
 mod debug;
 mod draw;
 pub mod main;
+mod settings;
 
 pub trait UiWindow: Sized {
     fn id() -> Id;
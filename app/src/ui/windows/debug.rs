@@ -64,6 +64,15 @@ impl UiWindow for DebugWindow<'_> {
                     ));
                     ui.end_row();
 
+                    ui.label("Sim TPS");
+                    let time_scale = if snapshot.time_scale.is_infinite() {
+                        "max".to_string()
+                    } else {
+                        format!("{}x", snapshot.time_scale)
+                    };
+                    ui.label(format!("{:.0} ({time_scale})", snapshot.effective_tps));
+                    ui.end_row();
+
                     ui.label("Sim Tick Time");
                     ui.label(format!("{:.2}ms", snapshot.avg_tick.as_secs_f32() * 1000.0));
                     ui.end_row();
@@ -71,6 +80,49 @@ impl UiWindow for DebugWindow<'_> {
                     ui.label("Discovered Asteroids");
                     ui.label(format!("{}", snapshot.discovered_asteroids));
                     ui.end_row();
+
+                    ui.label("Explored Cells");
+                    ui.label(format!("{}", snapshot.explored_cells));
+                    ui.end_row();
+
+                    ui.label("Population");
+                    ui.label(format!("{}", snapshot.population));
+                    ui.end_row();
+
+                    ui.label("Scouts");
+                    ui.label(format!("{}", snapshot.scouts));
+                    ui.end_row();
+
+                    ui.label("Miners");
+                    ui.label(format!("{}", snapshot.miners));
+                    ui.end_row();
+
+                    ui.label("Haulers");
+                    ui.label(format!("{}", snapshot.haulers));
+                    ui.end_row();
+
+                    ui.label("Depots");
+                    ui.label(format!("{}", snapshot.depots));
+                    ui.end_row();
+
+                    ui.label("Transport Requests");
+                    ui.label(format!("{}", snapshot.transport_requests));
+                    ui.end_row();
+
+                    if let Some(checksum) = snapshot.checksum {
+                        ui.label("State Checksum");
+                        ui.label(format!("{:016x} @ {}", checksum.value, checksum.tick));
+                        ui.end_row();
+                    }
+
+                    let drawn: Vec<_> = snapshot
+                        .drawn_layers
+                        .iter()
+                        .map(|l| l.to_string())
+                        .collect();
+                    ui.label("Drawn Layers");
+                    ui.label(drawn.join(", "));
+                    ui.end_row();
                 }
 
                 ui.label("Camera Center");
@@ -78,7 +130,12 @@ impl UiWindow for DebugWindow<'_> {
                 ui.end_row();
 
                 ui.label("Camera Zoom");
-                ui.label(format!("{:.2}x", self.app_ctx.camera.zoom));
+                let zoom = self.app_ctx.camera.zoom;
+                if zoom < 1.0 {
+                    ui.label(format!("1/{:.0}x", 1.0 / zoom));
+                } else {
+                    ui.label(format!("{zoom:.2}x"));
+                }
                 ui.end_row();
 
                 ui.label("Cursor (Screen)");
@@ -93,6 +150,79 @@ impl UiWindow for DebugWindow<'_> {
                 ui.label(format!("{}", self.app_ctx.screen_size));
                 ui.end_row();
             });
+
+        if let Some(snapshot) = self.app_ctx.sim_snapshot {
+            ui.collapsing("Colonies", |ui| {
+                for colony in &snapshot.colonies {
+                    let position = colony.position;
+                    ui.label(format!("Colony ({}, {})", position.x, position.y));
+                    Grid::new(("debug_colony_grid", position.x, position.y))
+                        .num_columns(2)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.label("Population");
+                            ui.label(format!("{}", colony.population));
+                            ui.end_row();
+
+                            ui.label("Generation");
+                            ui.label(format!("{}", colony.max_generation));
+                            ui.end_row();
+
+                            ui.label("Lineages");
+                            ui.label(format!("{}", colony.lineages.len()));
+                            ui.end_row();
+
+                            ui.label("Starving");
+                            ui.label(format!("{}", colony.production.starving));
+                            ui.end_row();
+
+                            ui.label("Resource Value");
+                            ui.label(format!("{:.1}", colony.resource_value));
+                            ui.end_row();
+
+                            let tps = snapshot.settings.tps as f32;
+                            for (resource, amount) in &colony.resources {
+                                let consumed = colony
+                                    .production
+                                    .consumed
+                                    .get(resource)
+                                    .copied()
+                                    .unwrap_or_default();
+                                ui.label(snapshot.resource_types.name(*resource));
+                                ui.label(format!("{amount:.1} (-{:.2}/s)", consumed * tps));
+                                ui.end_row();
+                            }
+
+                            for (product, amount) in &colony.products {
+                                let produced = colony
+                                    .production
+                                    .produced
+                                    .get(product)
+                                    .copied()
+                                    .unwrap_or_default();
+                                ui.label(product.to_string());
+                                ui.label(format!("{amount:.2} (+{:.3}/s)", produced * tps));
+                                ui.end_row();
+                            }
+
+                            for (gene, stats) in &colony.genes {
+                                ui.label(gene.to_string());
+                                ui.label(format!(
+                                    "{:.2} ({:.2}-{:.2})",
+                                    stats.mean, stats.min, stats.max
+                                ));
+                                ui.end_row();
+                            }
+                        });
+                }
+            });
+        }
+
+        ui.collapsing("Recent Events", |ui| {
+            for event in self.app_ctx.recent_events.iter().rev() {
+                ui.label(format!("{event:?}"));
+            }
+        });
     }
 }
 
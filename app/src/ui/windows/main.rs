@@ -1,13 +1,30 @@
+use crate::icons;
+use crate::ui::widgets::toggle_button::ToggleButton;
 use crate::ui::windows::debug::{DebugWindow, DebugWindowState};
 use crate::ui::windows::draw::{DrawWindow, DrawWindowState};
+use crate::ui::windows::settings::{SettingsWindow, SettingsWindowState};
 use crate::ui::windows::{ToggleableUiWindow, UiWindow};
 use crate::ui::AppContext;
-use egui::{Id, Ui, WidgetText};
+use egui::{Id, Ui, Widget, WidgetText};
+use pss_core::simulation::layer::Layer;
+use pss_core::simulation::sync::command::SimCommand;
+
+const SAVE_PATH: &str = "world.pss";
+const RECORDING_PATH: &str = "recording.pss";
+const STEP_TICKS: u64 = 60;
+const TIME_SCALES: [(f32, &str); 5] = [
+    (1.0, "1x"),
+    (2.0, "2x"),
+    (5.0, "5x"),
+    (10.0, "10x"),
+    (f32::INFINITY, "Max"),
+];
 
 pub struct MainWindowState {
     pub is_open: bool,
     pub draw: DrawWindowState,
     debug: DebugWindowState,
+    settings: SettingsWindowState,
 }
 
 impl Default for MainWindowState {
@@ -16,6 +33,7 @@ impl Default for MainWindowState {
             is_open: true,
             draw: Default::default(),
             debug: Default::default(),
+            settings: Default::default(),
         }
     }
 }
@@ -56,6 +74,85 @@ impl UiWindow for MainWindow<'_> {
             DrawWindow::new(&mut self.state.draw, self.app_ctx)
                 .toggle_button(ui)
                 .show(ui.ctx());
+            SettingsWindow::new(&mut self.state.settings, self.app_ctx)
+                .toggle_button(ui)
+                .show(ui.ctx());
+
+            ui.separator();
+
+            if let Some(sim) = self.app_ctx.simulation {
+                if let Some(snapshot) = self.app_ctx.sim_snapshot {
+                    let mut paused = snapshot.paused;
+                    if ToggleButton::new(&mut paused, icons::PAUSE)
+                        .tooltip("Pause")
+                        .ui(ui)
+                        .clicked()
+                    {
+                        sim.send_command(SimCommand::TogglePause);
+                    }
+                    if ui
+                        .button(icons::SKIP_FORWARD)
+                        .on_hover_text(format!("Step {STEP_TICKS} ticks"))
+                        .clicked()
+                    {
+                        sim.send_command(SimCommand::StepTicks(STEP_TICKS));
+                    }
+                    for (scale, label) in TIME_SCALES {
+                        if ui
+                            .selectable_label(snapshot.time_scale == scale, label)
+                            .clicked()
+                        {
+                            sim.send_command(SimCommand::SetTimeScale(scale));
+                        }
+                    }
+
+                    ui.separator();
+                }
+
+                if ui
+                    .button(icons::FLOPPY_DISK)
+                    .on_hover_text(format!("Save to {SAVE_PATH}"))
+                    .clicked()
+                {
+                    sim.send_command(SimCommand::Save(SAVE_PATH.into()));
+                }
+                if ui
+                    .button(icons::FOLDER_OPEN)
+                    .on_hover_text(format!("Load from {SAVE_PATH}"))
+                    .clicked()
+                {
+                    sim.send_command(SimCommand::Load(SAVE_PATH.into()));
+                }
+
+                let recording = self.app_ctx.sim_snapshot.is_some_and(|s| s.recording);
+                if recording {
+                    if ui
+                        .button(icons::STOP)
+                        .on_hover_text(format!("Stop recording and save to {RECORDING_PATH}"))
+                        .clicked()
+                    {
+                        sim.send_command(SimCommand::StopRecording(RECORDING_PATH.into()));
+                    }
+                } else if ui
+                    .button(icons::RECORD)
+                    .on_hover_text("Start recording commands")
+                    .clicked()
+                {
+                    sim.send_command(SimCommand::StartRecording);
+                }
+            }
         });
+
+        if let (Some(sim), Some(snapshot)) = (self.app_ctx.simulation, self.app_ctx.sim_snapshot) {
+            ui.horizontal(|ui| {
+                ui.label("Layers");
+                for layer in Layer::ALL {
+                    let mut visible = snapshot.visible_layers.contains(layer);
+                    if ui.checkbox(&mut visible, layer.to_string()).changed() {
+                        sim.send_command(SimCommand::SetLayerVisibility(layer, visible));
+                    }
+                }
+            });
+        }
     }
 }
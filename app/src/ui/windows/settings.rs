@@ -0,0 +1,239 @@
+use crate::icons;
+use crate::ui::windows::{ToggleableUiWindow, UiWindow};
+use crate::ui::AppContext;
+use egui::{DragValue, Grid, Id, Ui, WidgetText};
+use pss_core::simulation::state::settings::SimulationSettings;
+use pss_core::simulation::sync::command::SimCommand;
+use std::time::Duration;
+
+#[derive(Default)]
+pub struct SettingsWindowState {
+    pub is_open: bool,
+    /// Settings being edited, taken from the snapshot when the window opens.
+    draft: Option<SimulationSettings>,
+}
+
+pub struct SettingsWindow<'a> {
+    state: &'a mut SettingsWindowState,
+    app_ctx: &'a AppContext<'a>,
+}
+
+impl<'a> SettingsWindow<'a> {
+    pub fn new(state: &'a mut SettingsWindowState, app_ctx: &'a AppContext<'a>) -> Self {
+        Self { state, app_ctx }
+    }
+}
+
+impl UiWindow for SettingsWindow<'_> {
+    fn id() -> Id {
+        Id::new("settings_window")
+    }
+
+    fn title() -> impl Into<WidgetText> {
+        "Settings"
+    }
+
+    fn is_open(&self) -> bool {
+        self.state.is_open
+    }
+
+    fn set_open(&mut self, open: bool) {
+        self.state.is_open = open;
+        if !open {
+            self.state.draft = None;
+        }
+    }
+
+    fn render_content(&mut self, ui: &mut Ui) {
+        let (Some(sim), Some(snapshot)) = (self.app_ctx.simulation, self.app_ctx.sim_snapshot)
+        else {
+            ui.label("No simulation running");
+            return;
+        };
+
+        let settings = self
+            .state
+            .draft
+            .get_or_insert_with(|| snapshot.settings.clone());
+        let mut changed = false;
+
+        Grid::new("settings_grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Ticks per Second");
+                changed |= ui
+                    .add(DragValue::new(&mut settings.tps).range(1..=10_000))
+                    .changed();
+                ui.end_row();
+
+                ui.label("Frames per Second");
+                changed |= ui
+                    .add(DragValue::new(&mut settings.fps).range(1..=240))
+                    .changed();
+                ui.end_row();
+
+                ui.label("Visible Update Cooldown");
+                let mut cooldown_ms = settings.visible_update_cooldown.as_millis() as u64;
+                if ui
+                    .add(
+                        DragValue::new(&mut cooldown_ms)
+                            .range(0..=5_000)
+                            .suffix("ms"),
+                    )
+                    .changed()
+                {
+                    settings.visible_update_cooldown = Duration::from_millis(cooldown_ms);
+                    changed = true;
+                }
+                ui.end_row();
+
+                ui.label("Asteroid Density");
+                changed |= ui
+                    .add(
+                        DragValue::new(&mut settings.asteroid_density)
+                            .range(0.0..=1.0)
+                            .speed(0.00001)
+                            .max_decimals(5),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Max Asteroid Scale");
+                changed |= ui
+                    .add(
+                        DragValue::new(&mut settings.max_asteroid_scale)
+                            .range(0.1..=100.0)
+                            .speed(0.1),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Max Asteroid Amount");
+                changed |= ui
+                    .add(
+                        DragValue::new(&mut settings.max_asteroid_resource_amount)
+                            .range(1.0..=100_000.0)
+                            .speed(10.0),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Density Noise Scale");
+                changed |= ui
+                    .add(
+                        DragValue::new(&mut settings.density_noise_scale)
+                            .range(0.0..=10_000.0)
+                            .speed(10.0),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Density Noise Octaves");
+                changed |= ui
+                    .add(DragValue::new(&mut settings.density_noise_octaves).range(1..=8))
+                    .changed();
+                ui.end_row();
+
+                ui.label("Void Threshold");
+                changed |= ui
+                    .add(
+                        DragValue::new(&mut settings.density_void_threshold)
+                            .range(0.0..=0.49)
+                            .speed(0.005),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Resource Noise Scale");
+                changed |= ui
+                    .add(
+                        DragValue::new(&mut settings.resource_noise_scale)
+                            .range(0.0..=10_000.0)
+                            .speed(10.0),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Resource Noise Contrast");
+                changed |= ui
+                    .add(
+                        DragValue::new(&mut settings.resource_noise_contrast)
+                            .range(0.0..=8.0)
+                            .speed(0.05),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Cluster Chance");
+                changed |= ui
+                    .add(
+                        DragValue::new(&mut settings.cluster_chance)
+                            .range(0.0..=1.0)
+                            .speed(0.01),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Cluster Density");
+                changed |= ui
+                    .add(
+                        DragValue::new(&mut settings.cluster_density)
+                            .range(0.0..=0.1)
+                            .speed(0.0001),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Belt Chance");
+                changed |= ui
+                    .add(
+                        DragValue::new(&mut settings.belt_chance)
+                            .range(0.0..=1.0)
+                            .speed(0.01),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Belt Density");
+                changed |= ui
+                    .add(
+                        DragValue::new(&mut settings.belt_density)
+                            .range(0.0..=0.1)
+                            .speed(0.0001),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Mixed Compositions");
+                changed |= ui.checkbox(&mut settings.mixed_compositions, "").changed();
+                ui.end_row();
+            });
+
+        match settings.validate() {
+            Ok(()) if changed => {
+                sim.send_command(SimCommand::UpdateSettings(Box::new(settings.clone())));
+            }
+            Ok(()) => {}
+            Err(error) => {
+                ui.colored_label(ui.visuals().error_fg_color, error.to_string());
+            }
+        }
+
+        ui.separator();
+
+        if ui
+            .button("Reset")
+            .on_hover_text("Discard edits and show the current settings")
+            .clicked()
+        {
+            self.state.draft = None;
+        }
+    }
+}
+
+impl ToggleableUiWindow for SettingsWindow<'_> {
+    fn toggle_label(&self) -> String {
+        icons::GEAR.into()
+    }
+}
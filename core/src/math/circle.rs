@@ -10,6 +10,14 @@ impl<N> Circle<N> {
     pub fn new(center: Point<N>, radius: N) -> Self {
         Self { center, radius }
     }
+
+    pub fn center(&self) -> &Point<N> {
+        &self.center
+    }
+
+    pub fn radius(&self) -> &N {
+        &self.radius
+    }
 }
 
 impl Circle<f32> {
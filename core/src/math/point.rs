@@ -1,7 +1,7 @@
 use std::fmt::Display;
 use std::ops::{Add, Div, Mul, Sub};
 
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Point<N> {
     pub x: N,
     pub y: N,
@@ -71,6 +71,31 @@ impl Point<f32> {
     pub fn floor(self) -> Self {
         self.map(|n| n.floor())
     }
+
+    pub fn from_angle(radians: f32) -> Self {
+        Point::new(radians.cos(), radians.sin())
+    }
+
+    pub fn length(self) -> f32 {
+        (self.x * self.x + self.y * self.y).sqrt()
+    }
+
+    pub fn distance(self, other: Self) -> f32 {
+        (other - self).length()
+    }
+
+    /// Moves towards the target by at most `max_distance`, returns true if the target was reached.
+    pub fn step_towards(&mut self, target: Self, max_distance: f32) -> bool {
+        let delta = target - *self;
+        let distance = delta.length();
+        if distance <= max_distance {
+            *self = target;
+            true
+        } else {
+            *self = *self + delta / distance * max_distance;
+            false
+        }
+    }
 }
 
 impl Point<i64> {
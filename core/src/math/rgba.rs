@@ -1,6 +1,6 @@
 use std::ops::Deref;
 
-#[derive(Default, Copy, Clone)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 #[repr(transparent)]
 pub struct RGBA([u8; 4]);
 
@@ -22,6 +22,12 @@ impl RGBA {
         )
     }
 
+    /// Scales the color channels by `factor`, keeping alpha.
+    pub fn shaded(self, factor: f32) -> Self {
+        let scale = |c: u8| (c as f32 * factor).clamp(0.0, 255.0) as u8;
+        Self::new(scale(self.r()), scale(self.g()), scale(self.b()), self.a())
+    }
+
     pub fn r(&self) -> u8 {
         self.0[0]
     }
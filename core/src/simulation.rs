@@ -1,37 +1,62 @@
 use crate::math::area::Area;
+use crate::math::circle::Circle;
 use crate::math::point::Point;
 use crate::math::rect::Rect;
 use crate::math::rgba::RGBA;
 use crate::math::size::Size;
 use crate::simulation::frame::SimFrame;
-use crate::simulation::procedural::asteroid_shape::asteroid_shape_eclipse;
+use crate::simulation::layer::{Layer, LayerSet};
+use crate::simulation::procedural::asteroid_shape::AsteroidShape;
+use crate::simulation::replay::Replay;
+use crate::simulation::state::hauler::HaulerTask;
+use crate::simulation::state::logistics::{Stockpile, TransportRequest};
+use crate::simulation::state::miner::MinerTask;
+use crate::simulation::state::ship::{ShipId, ShipKind};
 use crate::simulation::state::SimState;
 use rayon::iter::ParallelIterator;
 use rayon::prelude::IntoParallelRefIterator;
 use state::settings;
-use std::collections::{HashSet, VecDeque};
-use std::ops::Add;
-use std::time::Instant;
+use std::collections::{BTreeSet, VecDeque};
+use std::path::Path;
+use std::time::Duration;
 use sync::command::SimCommand;
 use sync::{event, snapshot};
 
 mod frame;
+pub mod layer;
 mod procedural;
+pub mod replay;
 pub mod source;
 pub mod state;
 pub mod sync;
 
+/// Asteroids smaller than this are drawn in their blended color instead of dithered.
+const DITHER_MIN_SCALE: f32 = 2.0;
+const CARGO_EPSILON: f32 = 1e-3;
+/// Range finite time scales are clamped to, keeping the tick interval representable.
+const MIN_TIME_SCALE: f32 = 0.01;
+const MAX_TIME_SCALE: f32 = 1000.0;
+/// Haze over unexplored space, explored empty space stays black.
+const FOG_COLOR: RGBA = RGBA::new(36, 40, 56, 160);
+
 pub struct Simulation {
     state: SimState,
     event_queue: VecDeque<event::SimEvent>,
     visible_rect: Rect<f32>,
     screen_size: Size<u32>,
-    ticks: u64,
+    visible_layers: LayerSet,
     alive: bool,
     paused: bool,
-    debounce_visible: Option<Instant>,
-    visible_asteroids: HashSet<Point<i64>>,
-    visible_colonies: HashSet<Point<i64>>,
+    /// Multiplier on the configured tps, infinite runs ticks as fast as possible.
+    time_scale: f32,
+    /// Tick to run to as fast as possible before pausing.
+    run_until: Option<u64>,
+    /// Frames left until the visible sets are refreshed.
+    debounce_visible: Option<u32>,
+    visible_asteroids: BTreeSet<Point<i64>>,
+    visible_colonies: BTreeSet<Point<i64>>,
+    checksum: Option<snapshot::StateChecksum>,
+    recording: Option<Replay>,
 }
 
 impl Simulation {
@@ -41,25 +66,63 @@ impl Simulation {
             event_queue: VecDeque::new(),
             visible_rect: Rect::default(),
             screen_size: Size::new(1, 1),
-            ticks: 0,
+            visible_layers: LayerSet::default(),
             alive: true,
             paused: false,
+            time_scale: 1.0,
+            run_until: None,
             debounce_visible: None,
             visible_asteroids: Default::default(),
             visible_colonies: Default::default(),
+            checksum: None,
+            recording: None,
         }
     }
 
     pub fn tick(&mut self, force: bool) {
-        if !force && self.paused {
+        if !force && !self.is_running() {
             return;
         }
-        self.ticks = self.ticks.wrapping_add(1);
+        self.state.advance_tick();
+
+        self.launch_scouts();
+        self.update_scouts();
+        self.launch_miners();
+        self.update_miners();
+        self.update_logistics();
+        self.update_haulers();
+        self.update_production();
+        self.update_population();
+        self.expand_colonies();
+
+        let tick = self.state.tick();
+        if tick.is_multiple_of(self.state.settings.checksum_interval) {
+            self.checksum = Some(snapshot::StateChecksum {
+                tick,
+                value: self.state.checksum(),
+            });
+        }
+
+        if self.run_until.is_some_and(|target| tick >= target) {
+            self.run_until = None;
+            self.set_paused(true);
+            self.push_event(event::SimEvent::TickReached(tick));
+        }
+    }
+
+    /// Advances exactly `ticks` ticks regardless of pause state or wall clock,
+    /// so the same state and commands always produce the same result.
+    pub fn step(&mut self, ticks: u64) {
+        for _ in 0..ticks {
+            self.tick(true);
+        }
     }
 
     pub fn update_frame(&mut self, frame: &mut SimFrame) {
-        if frame.visible_rect() != self.visible_rect {
+        let cell_size = self.lod_cell_size();
+        if frame.visible_rect() != self.visible_rect || frame.cell_size() != cell_size {
             frame.set_visible_rect(self.visible_rect);
+            frame.set_cell_size(cell_size);
             frame.resize_to_visible_rect();
             self.debounce_update_visible();
         }
@@ -67,82 +130,302 @@ impl Simulation {
 
         self.update_visible(false);
 
+        let layers = self.visible_layers;
+        if layers.contains(Layer::Asteroids) {
+            if frame.is_lod() {
+                self.draw_asteroids_lod(frame);
+            } else {
+                self.draw_asteroids(frame);
+            }
+        }
+        if layers.contains(Layer::Colonies) {
+            self.draw_colonies(frame);
+        }
+        if layers.contains(Layer::Ships) {
+            self.draw_ships(frame);
+        }
+        if layers.contains(Layer::Fog) {
+            frame.fill_fog(Layer::Fog, &self.state.explored, FOG_COLOR);
+        }
+        if layers.contains(Layer::Debug) {
+            self.draw_debug(frame);
+        }
+        frame.composite();
+
+        self.update_snapshot(&mut frame.snapshot);
+        frame.snapshot.drawn_layers = frame.drawn_layers();
+    }
+
+    /// World cells per frame pixel: 1 while every cell gets at least one screen pixel,
+    /// otherwise the next power of two so the frame stays about screen sized.
+    fn lod_cell_size(&self) -> i64 {
+        let cells_per_pixel = (self.visible_rect.width() / self.screen_size.width.max(1) as f32)
+            .max(self.visible_rect.height() / self.screen_size.height.max(1) as f32);
+        if cells_per_pixel <= 1.0 {
+            1
+        } else {
+            (cells_per_pixel.ceil() as u64).next_power_of_two() as i64
+        }
+    }
+
+    fn draw_asteroids(&self, frame: &mut SimFrame) {
         for point in self.visible_asteroids.iter() {
-            let Some(resource_type) = self.state.resource_type_at(*point) else {
+            let Some(composition) = self.state.composition_at(*point) else {
                 continue;
             };
-            let Some(scale) = self.state.asteroid_scale_at(*point) else {
+            let Some(scale) = self.state.asteroid_scale_at(*point, &composition) else {
                 continue;
             };
             let shape_seed = self.state.asteroid_shape_seed(*point);
-            let eclipse = asteroid_shape_eclipse(shape_seed, point.to_f32(), scale);
-            frame.fill_ellipse(eclipse, resource_type.into());
+            let shape = AsteroidShape::new(shape_seed, *point, scale);
+            // Too few cells to make out a dithered mix, so small asteroids show the average.
+            let palette = if scale < DITHER_MIN_SCALE {
+                vec![(composition.blended_color(&self.state.resource_types), 1.0)]
+            } else {
+                composition.palette(&self.state.resource_types)
+            };
+            frame.fill_asteroid(Layer::Asteroids, &shape, &palette);
+        }
+    }
+
+    /// Asteroids are far smaller than a pixel, so each pixel shows the mix of those it covers.
+    fn draw_asteroids_lod(&self, frame: &mut SimFrame) {
+        for point in self.visible_asteroids.iter() {
+            let Some(composition) = self.state.composition_at(*point) else {
+                continue;
+            };
+            frame.accumulate(
+                *point,
+                composition.blended_color(&self.state.resource_types),
+            );
         }
+        frame.resolve_accumulated(Layer::Asteroids);
+    }
 
+    fn draw_colonies(&self, frame: &mut SimFrame) {
         for point in self.visible_colonies.iter() {
             let Some(_colony) = self.state.colony_at(*point) else {
                 continue;
             };
             let rect = Rect::new_square(point.to_f32(), 10.0);
-            frame.fill_rect(rect, RGBA::rgb(70, 70, 70));
+            frame.fill_rect(Layer::Colonies, rect, RGBA::rgb(70, 70, 70));
         }
 
-        self.update_snapshot(&mut frame.snapshot);
+        for point in self.state.depots.keys() {
+            let rect = Rect::new_square(point.to_f32(), 4.0);
+            frame.fill_rect(Layer::Colonies, rect, RGBA::rgb(110, 110, 140));
+        }
+    }
+
+    fn draw_ships(&self, frame: &mut SimFrame) {
+        for scout in self.state.scouts.values() {
+            frame.fill_cell(Layer::Ships, scout.position, RGBA::white());
+        }
+
+        for miner in self.state.miners.values() {
+            frame.fill_cell(Layer::Ships, miner.position, RGBA::yellow());
+        }
+
+        for hauler in self.state.haulers.values() {
+            frame.fill_cell(Layer::Ships, hauler.position, RGBA::magenta());
+        }
+    }
+
+    /// Marks what ships are headed for: miner targets in red, colonies awaiting shipments in cyan.
+    fn draw_debug(&self, frame: &mut SimFrame) {
+        for target in self
+            .state
+            .miners
+            .values()
+            .filter_map(|miner| miner.target())
+        {
+            frame.fill_cell(Layer::Debug, target.to_f32(), RGBA::red());
+        }
+
+        for request in &self.state.transport_requests {
+            let rect = Rect::new_square(request.colony.to_f32(), 2.0);
+            frame.fill_rect(Layer::Debug, rect, RGBA::cyan());
+        }
     }
 
     fn debounce_update_visible(&mut self) {
         if self.debounce_visible.is_none() {
-            self.debounce_visible =
-                Some(Instant::now().add(self.state.settings.visible_update_cooldown));
+            self.debounce_visible = Some(self.state.settings.visible_update_cooldown_frames());
         }
     }
 
     fn update_visible(&mut self, force: bool) {
         if !force {
-            let Some(debounce) = &self.debounce_visible else {
+            let Some(frames) = &mut self.debounce_visible else {
                 return;
             };
 
-            if Instant::now() < *debounce {
+            if *frames > 0 {
+                *frames -= 1;
                 return;
             }
         }
 
-        self.visible_asteroids.clear();
-        self.state.discovered_asteroids.keys().for_each(|point| {
-            if self.visible_rect.contains(point.to_f32()) {
-                self.visible_asteroids.insert(*point);
-            }
-        });
+        let rect = self.visible_rect.floor().to_i64();
+        let visible = |point: &Point<i64>| self.visible_rect.contains(point.to_f32());
 
-        self.visible_colonies.clear();
-        self.state.colonies.keys().for_each(|point| {
-            if self.visible_rect.contains(point.to_f32()) {
-                self.visible_colonies.insert(*point);
-            }
-        });
+        self.visible_asteroids = self
+            .state
+            .asteroid_index
+            .query_rect(rect)
+            .filter(visible)
+            .collect();
+        self.visible_colonies = self
+            .state
+            .colony_index
+            .query_rect(rect)
+            .filter(visible)
+            .collect();
 
         self.debounce_visible = None;
     }
 
-    fn update_snapshot(&self, snapshot: &mut snapshot::SimSnapshot) {
+    pub fn update_snapshot(&self, snapshot: &mut snapshot::SimSnapshot) {
         snapshot.discovered_asteroids = self.state.discovered_asteroids.len();
+        snapshot.explored_cells = self.state.explored.explored_count();
+        snapshot.scouts = self.state.scouts.len();
+        snapshot.miners = self.state.miners.len();
+        snapshot.haulers = self.state.haulers.len();
+        snapshot.population = self.state.population();
+        snapshot.colonies = self
+            .state
+            .colonies
+            .iter()
+            .map(|(point, colony)| {
+                snapshot::ColonySnapshot::new(
+                    *point,
+                    colony,
+                    self.state.colony_members(*point),
+                    &self.state.resource_types,
+                )
+            })
+            .collect();
+        snapshot.depots = self.state.depots.len();
+        snapshot.transport_requests = self.state.transport_requests.len();
         snapshot.settings = self.state.settings.clone();
+        if snapshot.resource_types != self.state.resource_types {
+            snapshot.resource_types = self.state.resource_types.clone();
+        }
+        snapshot.checksum = self.checksum;
+        snapshot.recording = self.recording.is_some();
+        snapshot.paused = self.paused;
+        snapshot.time_scale = self.time_scale;
+        snapshot.run_until = self.run_until;
+        snapshot.visible_layers = self.visible_layers;
     }
 
     pub fn handle_command(&mut self, command: SimCommand) {
+        if let Some(recording) = &mut self.recording {
+            recording.record(self.state.tick(), &command);
+        }
+
         match command {
             SimCommand::Clear => {}
-            SimCommand::Pause => self.paused = true,
-            SimCommand::Resume => self.paused = false,
-            SimCommand::TogglePause => self.paused = !self.paused,
+            SimCommand::Pause => self.set_paused(true),
+            SimCommand::Resume => self.set_paused(false),
+            SimCommand::TogglePause => self.set_paused(!self.paused),
             SimCommand::Shutdown => self.alive = false,
             SimCommand::SetVisibleRect(rect) => self.visible_rect = rect,
             SimCommand::SetScreenSize(size) => self.screen_size = size,
+            SimCommand::SetLayerVisibility(layer, visible) => {
+                self.visible_layers.set(layer, visible)
+            }
             SimCommand::ScoutArea(area) => self.scout_area(area),
+            SimCommand::FoundColony(point) => self.found_colony(point),
+            SimCommand::Save(path) => self.save(&path),
+            SimCommand::Load(path) => self.load(&path),
+            SimCommand::SetTimeScale(scale) => self.set_time_scale(scale),
+            SimCommand::StepTicks(ticks) => self.run_until(self.state.tick().saturating_add(ticks)),
+            SimCommand::RunUntilTick(tick) => self.run_until(tick),
+            SimCommand::UpdateSettings(settings) => {
+                if let Err(error) = self.update_settings(*settings) {
+                    self.push_event(event::SimEvent::SettingsRejected(error.to_string()));
+                }
+            }
+            SimCommand::StartRecording => self.start_recording(),
+            SimCommand::StopRecording(path) => self.stop_recording(&path),
+        }
+    }
+
+    fn save(&mut self, path: &Path) {
+        match self.state.save_to(path) {
+            Ok(()) => self.push_event(event::SimEvent::StateSaved),
+            Err(error) => self.push_event(event::SimEvent::SaveFailed(error.to_string())),
+        }
+    }
+
+    fn load(&mut self, path: &Path) {
+        match SimState::load_from(path) {
+            Ok(state) => {
+                self.state = state;
+                self.update_visible(true);
+                // Commands before the load can't be replayed, so the recording restarts here.
+                if self.recording.is_some() {
+                    self.recording = Some(Replay::new(&self.state));
+                }
+                self.push_event(event::SimEvent::StateLoaded);
+            }
+            Err(error) => self.push_event(event::SimEvent::LoadFailed(error.to_string())),
+        }
+    }
+
+    fn start_recording(&mut self) {
+        self.recording = Some(Replay::new(&self.state));
+        self.push_event(event::SimEvent::RecordingStarted);
+    }
+
+    fn stop_recording(&mut self, path: &Path) {
+        let Some(recording) = self.recording.take() else {
+            let error = "No recording in progress".to_string();
+            self.push_event(event::SimEvent::RecordingFailed(error));
+            return;
+        };
+
+        match recording.save_to(path) {
+            Ok(()) => self.push_event(event::SimEvent::RecordingSaved),
+            Err(error) => self.push_event(event::SimEvent::RecordingFailed(error.to_string())),
+        }
+    }
+
+    fn set_time_scale(&mut self, scale: f32) {
+        if scale.is_nan() || scale <= 0.0 {
+            return;
+        }
+        let scale = if scale.is_infinite() {
+            scale
+        } else {
+            scale.clamp(MIN_TIME_SCALE, MAX_TIME_SCALE)
+        };
+        if scale != self.time_scale {
+            self.time_scale = scale;
+            self.push_event(event::SimEvent::TimeScaleChanged(scale));
+        }
+    }
+
+    fn run_until(&mut self, tick: u64) {
+        self.run_until = (tick > self.state.tick()).then_some(tick);
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        if self.paused != paused {
+            self.paused = paused;
+            self.push_event(event::SimEvent::PauseChanged(paused));
         }
     }
 
+    fn push_event(&mut self, event: event::SimEvent) {
+        self.event_queue.push_back(event);
+    }
+
+    pub fn poll_event(&mut self) -> Option<event::SimEvent> {
+        self.event_queue.pop_front()
+    }
+
     pub fn is_alive(&self) -> bool {
         self.alive
     }
@@ -151,6 +434,31 @@ impl Simulation {
         self.paused
     }
 
+    /// Whether ticks currently advance, either unpaused or running to a target tick.
+    pub fn is_running(&self) -> bool {
+        match self.run_until {
+            Some(target) => self.state.tick() < target,
+            None => !self.paused,
+        }
+    }
+
+    /// Wall-clock time between ticks, `None` when ticks are unbounded.
+    pub fn tick_interval(&self) -> Option<Duration> {
+        if self.run_until.is_some() || self.time_scale.is_infinite() {
+            return None;
+        }
+        Some(
+            self.state
+                .settings
+                .interval_tps()
+                .div_f64(self.time_scale as f64),
+        )
+    }
+
+    pub fn state(&self) -> &SimState {
+        &self.state
+    }
+
     pub fn settings(&self) -> &settings::SimulationSettings {
         &self.state.settings
     }
@@ -159,12 +467,549 @@ impl Simulation {
 // World updates
 impl Simulation {
     pub fn discover_asteroid(&mut self, point: Point<i64>) {
-        self.state.discovered_asteroids.insert(point, 0.0);
-        self.update_visible(true);
+        self.state.discover_asteroid(point);
+        self.push_event(event::SimEvent::AsteroidDiscovered(point));
+        if self.visible_rect.contains(point.to_f32()) {
+            self.visible_asteroids.insert(point);
+        }
+    }
+
+    pub fn found_colony(&mut self, point: Point<i64>) {
+        if self.state.found_colony(point) {
+            self.on_colony_founded(point);
+        }
+    }
+
+    fn on_colony_founded(&mut self, point: Point<i64>) {
+        self.push_event(event::SimEvent::ColonyFounded(point));
+        if self.visible_rect.contains(point.to_f32()) {
+            self.visible_colonies.insert(point);
+        }
+    }
+
+    fn expand_colonies(&mut self) {
+        let interval = self.state.settings.expansion_interval.max(1);
+        if !self.state.tick().is_multiple_of(interval) {
+            return;
+        }
+
+        let colonies: Vec<_> = self.state.colonies.keys().copied().collect();
+        for colony in colonies {
+            if !self.state.can_expand(colony) {
+                continue;
+            }
+            let Some(site) = self.state.colony_site(colony) else {
+                continue;
+            };
+            if self.state.settle_colony(colony, site) {
+                self.on_colony_founded(site);
+            }
+        }
+    }
+
+    pub fn update_settings(
+        &mut self,
+        settings: settings::SimulationSettings,
+    ) -> Result<(), settings::SettingsError> {
+        settings.validate()?;
+        self.state.settings = settings;
+        self.push_event(event::SimEvent::SettingsChanged);
+        Ok(())
+    }
+
+    fn launch_scouts(&mut self) {
+        let interval = self.state.settings.scout_launch_interval.max(1);
+        if !self.state.tick().is_multiple_of(interval) {
+            return;
+        }
+
+        let max_scouts = self.state.settings.max_scouts_per_colony;
+        let homes: Vec<_> = self
+            .state
+            .colonies
+            .keys()
+            .filter(|home| self.state.scouts_from(**home) < max_scouts)
+            .copied()
+            .collect();
+
+        for home in homes {
+            let Some(id) = self.state.launch_scout(home) else {
+                continue;
+            };
+            self.push_event(event::SimEvent::ShipLaunched {
+                id,
+                kind: ShipKind::Scout,
+                from: home,
+            });
+        }
+    }
+
+    fn update_scouts(&mut self) {
+        let speed = self.state.settings.scout_speed;
+        let range = self.state.settings.scout_range;
+        let sensor_radius = self.state.settings.scout_sensor_radius;
+
+        let mut scans = Vec::new();
+        let mut arrived = Vec::new();
+        for (id, scout) in self.state.scouts.iter_mut() {
+            let genome = scout.pilot.genome;
+            if scout.advance(speed * genome.speed(), range * genome.risk_tolerance()) {
+                arrived.push(*id);
+            } else if !scout.returning {
+                let radius = sensor_radius * genome.perception();
+                scans.push(Circle::new(scout.position, radius));
+            }
+        }
+
+        for circle in scans {
+            self.scout_area(Area::Circle(circle));
+        }
+
+        for id in arrived {
+            let Some(scout) = self.state.scouts.remove(&id) else {
+                continue;
+            };
+            self.state.dock_ship(scout.home, scout.pilot);
+            self.push_event(event::SimEvent::ShipArrived {
+                id,
+                kind: ShipKind::Scout,
+                at: scout.home,
+            });
+        }
+    }
+
+    pub fn deplete_asteroid(&mut self, point: Point<i64>) {
+        self.state.deplete_asteroid(point);
+        self.visible_asteroids.remove(&point);
+        self.push_event(event::SimEvent::AsteroidDepleted(point));
+    }
+
+    fn launch_miners(&mut self) {
+        let interval = self.state.settings.miner_launch_interval.max(1);
+        if !self.state.tick().is_multiple_of(interval) {
+            return;
+        }
+
+        let max_miners = self.state.settings.max_miners_per_colony;
+        let range = self.state.settings.miner_range;
+        let homes: Vec<_> = self
+            .state
+            .colonies
+            .keys()
+            .filter(|home| self.state.miners_from(**home) < max_miners)
+            .copied()
+            .collect();
+
+        for home in homes {
+            let Some(target) = self.state.nearest_unclaimed_asteroid(home.to_f32(), range) else {
+                continue;
+            };
+            let Some(id) = self.state.launch_miner(home, target) else {
+                continue;
+            };
+            self.push_event(event::SimEvent::ShipLaunched {
+                id,
+                kind: ShipKind::Miner,
+                from: home,
+            });
+        }
+    }
+
+    fn update_miners(&mut self) {
+        let ids: Vec<_> = self.state.miners.keys().copied().collect();
+        for id in ids {
+            self.update_miner(id);
+        }
+    }
+
+    fn update_miner(&mut self, id: ShipId) {
+        let speed = self.state.settings.miner_speed;
+        let capacity = self.state.settings.miner_capacity;
+        let rate = self.state.settings.mining_rate;
+
+        let Some((task, genome)) = self
+            .state
+            .miners
+            .get(&id)
+            .map(|miner| (miner.task, miner.pilot.genome))
+        else {
+            return;
+        };
+        let bonus = self.state.mining_bonus(id);
+        let speed = speed * genome.speed();
+        let rate = rate * genome.mining_efficiency() * bonus;
+
+        match task {
+            MinerTask::Travelling(target) => {
+                if !self.state.has_asteroid_resources(target) {
+                    self.retarget_miner(id);
+                    return;
+                }
+
+                let Some(miner) = self.state.miners.get_mut(&id) else {
+                    return;
+                };
+                if miner.position.step_towards(target.to_f32(), speed) {
+                    miner.task = MinerTask::Mining(target);
+                    self.push_event(event::SimEvent::ShipArrived {
+                        id,
+                        kind: ShipKind::Miner,
+                        at: target,
+                    });
+                }
+            }
+            MinerTask::Mining(target) => {
+                let Some(miner) = self.state.miners.get(&id) else {
+                    return;
+                };
+                let free_capacity = (capacity - miner.cargo.total_amount()).max(0.0);
+
+                let requested = rate.min(free_capacity);
+                let Some(mined) = self.state.mine_asteroid(target, requested) else {
+                    self.retarget_miner(id);
+                    return;
+                };
+
+                if let Some(miner) = self.state.miners.get_mut(&id) {
+                    for (resource, amount) in mined.iter() {
+                        miner.cargo.add(resource, amount);
+                    }
+                }
+
+                // Unless the asteroid ran out, exactly the requested amount was mined.
+                if self.state.resource_amount_at(target) == Some(0.0) {
+                    self.deplete_asteroid(target);
+                    self.retarget_miner(id);
+                } else if requested >= free_capacity {
+                    self.retarget_miner(id);
+                }
+            }
+            MinerTask::Delivering(depot) => {
+                let Some(miner) = self.state.miners.get_mut(&id) else {
+                    return;
+                };
+                if miner.position.step_towards(depot.to_f32(), speed) {
+                    self.unload_miner(id, depot);
+                }
+            }
+            MinerTask::Returning => {
+                let Some(miner) = self.state.miners.get_mut(&id) else {
+                    return;
+                };
+                if miner.position.step_towards(miner.home.to_f32(), speed) {
+                    self.dock_miner(id);
+                }
+            }
+        }
+    }
+
+    fn retarget_miner(&mut self, id: ShipId) {
+        let capacity = self.state.settings.miner_capacity;
+        let range = self.state.settings.miner_range;
+
+        let Some(miner) = self.state.miners.get(&id) else {
+            return;
+        };
+
+        let range = range * miner.pilot.genome.risk_tolerance();
+        // Mixed cargo is summed from several resources, so allow for rounding.
+        let task = if miner.cargo.total_amount() >= capacity - CARGO_EPSILON {
+            self.miner_drop_off(id)
+        } else {
+            self.state
+                .nearest_unclaimed_asteroid(miner.position, range)
+                .map_or(MinerTask::Returning, MinerTask::Travelling)
+        };
+
+        if let Some(miner) = self.state.miners.get_mut(&id) {
+            miner.task = task;
+        }
+    }
+
+    /// Full miners far away from home unload at the nearest depot, establishing one if needed.
+    fn miner_drop_off(&mut self, id: ShipId) -> MinerTask {
+        let depot_distance = self.state.settings.depot_distance;
+
+        let Some(miner) = self.state.miners.get(&id) else {
+            return MinerTask::Returning;
+        };
+        if miner.position.distance(miner.home.to_f32()) <= depot_distance {
+            return MinerTask::Returning;
+        }
+
+        if let Some(depot) = self.state.nearest_depot(miner.position, depot_distance) {
+            return MinerTask::Delivering(depot);
+        }
+
+        let point = miner.position.round().to_i64();
+        self.state.establish_depot(point);
+        self.push_event(event::SimEvent::DepotEstablished(point));
+        MinerTask::Delivering(point)
+    }
+
+    fn unload_miner(&mut self, id: ShipId, depot: Point<i64>) {
+        let Some(miner) = self.state.miners.get_mut(&id) else {
+            return;
+        };
+
+        if let Some(depot) = self.state.depots.get_mut(&depot) {
+            for (resource, amount) in miner.cargo.iter() {
+                depot.resources.add(resource, amount);
+            }
+            miner.cargo.clear();
+        }
+
+        self.retarget_miner(id);
+    }
+
+    fn dock_miner(&mut self, id: ShipId) {
+        let Some(miner) = self.state.miners.remove(&id) else {
+            return;
+        };
+
+        self.state.dock_ship(miner.home, miner.pilot);
+        if let Some(colony) = self.state.colonies.get_mut(&miner.home) {
+            for (resource, amount) in miner.cargo.iter() {
+                colony.resources.add(resource, amount);
+            }
+            for (resource, amount) in miner.cargo.iter() {
+                self.push_event(event::SimEvent::ResourcesDelivered {
+                    colony: miner.home,
+                    resource,
+                    amount,
+                });
+            }
+        }
+
+        self.push_event(event::SimEvent::ShipArrived {
+            id,
+            kind: ShipKind::Miner,
+            at: miner.home,
+        });
+    }
+
+    fn update_logistics(&mut self) {
+        let interval = self.state.settings.logistics_interval.max(1);
+        if !self.state.tick().is_multiple_of(interval) {
+            return;
+        }
+
+        self.state.post_transport_requests();
+
+        let max_haulers = self.state.settings.max_haulers_per_colony;
+        let homes: Vec<_> = self.state.colonies.keys().copied().collect();
+        for home in homes {
+            for _ in self.state.haulers_from(home)..max_haulers {
+                let Some((source, request)) = self.state.best_transport(home) else {
+                    break;
+                };
+                let Some(id) = self.state.launch_hauler(home, source, request) else {
+                    break;
+                };
+                self.push_event(event::SimEvent::ShipLaunched {
+                    id,
+                    kind: ShipKind::Hauler,
+                    from: home,
+                });
+            }
+        }
+    }
+
+    fn update_haulers(&mut self) {
+        let ids: Vec<_> = self.state.haulers.keys().copied().collect();
+        for id in ids {
+            self.update_hauler(id);
+        }
+    }
+
+    fn update_hauler(&mut self, id: ShipId) {
+        let speed = self.state.settings.hauler_speed;
+
+        let Some(hauler) = self.state.haulers.get_mut(&id) else {
+            return;
+        };
+        let speed = speed * hauler.pilot.genome.speed();
+
+        match hauler.task {
+            HaulerTask::Pickup(source, request) => {
+                if hauler.position.step_towards(source.point().to_f32(), speed) {
+                    self.load_hauler(id, source, request);
+                }
+            }
+            HaulerTask::Deliver(request) => {
+                if hauler.position.step_towards(request.colony.to_f32(), speed) {
+                    self.unload_hauler(id, request);
+                }
+            }
+            HaulerTask::Returning => {
+                if hauler.position.step_towards(hauler.home.to_f32(), speed) {
+                    let Some(hauler) = self.state.haulers.remove(&id) else {
+                        return;
+                    };
+                    self.state.dock_ship(hauler.home, hauler.pilot);
+                    self.push_event(event::SimEvent::ShipArrived {
+                        id,
+                        kind: ShipKind::Hauler,
+                        at: hauler.home,
+                    });
+                }
+            }
+        }
+    }
+
+    fn load_hauler(&mut self, id: ShipId, source: Stockpile, request: TransportRequest) {
+        let capacity = self.state.settings.hauler_capacity;
+        let amount = self
+            .state
+            .available_at(source, request.resource)
+            .min(request.amount)
+            .min(capacity);
+
+        let loaded = amount > 0.0
+            && self
+                .state
+                .stockpile_mut(source)
+                .is_some_and(|bag| bag.remove(request.resource, amount));
+
+        let Some(hauler) = self.state.haulers.get_mut(&id) else {
+            return;
+        };
+        if loaded {
+            hauler.cargo.add(request.resource, amount);
+            hauler.task = HaulerTask::Deliver(request);
+        } else {
+            hauler.task = HaulerTask::Returning;
+        }
+    }
+
+    fn unload_hauler(&mut self, id: ShipId, request: TransportRequest) {
+        let Some(hauler) = self.state.haulers.get_mut(&id) else {
+            return;
+        };
+
+        let amount = hauler.cargo.total(request.resource);
+        hauler.cargo.clear();
+        hauler.task = HaulerTask::Returning;
+
+        if let Some(colony) = self.state.colonies.get_mut(&request.colony) {
+            colony.resources.add(request.resource, amount);
+            self.push_event(event::SimEvent::ResourcesDelivered {
+                colony: request.colony,
+                resource: request.resource,
+                amount,
+            });
+        }
+    }
+
+    fn update_production(&mut self) {
+        let settings = &self.state.settings;
+        let mut shortages = Vec::new();
+        for (point, colony) in self.state.colonies.iter_mut() {
+            let was_starving = colony.production.starving;
+            colony.produce(&settings.recipes, settings.ice_upkeep_per_human);
+            if colony.production.starving && !was_starving {
+                shortages.push((*point, colony.production.ice_shortage));
+            }
+        }
+
+        for (colony, shortage) in shortages {
+            self.push_event(event::SimEvent::ColonyStarving { colony, shortage });
+        }
+    }
+
+    fn update_population(&mut self) {
+        let interval = self.state.settings.population_interval.max(1);
+        if !self.state.tick().is_multiple_of(interval) {
+            return;
+        }
+
+        let base_lifespan = self.state.settings.human_base_lifespan;
+        let mut deaths = Vec::new();
+
+        for colony in self.state.colonies.values_mut() {
+            colony.population.retain_mut(|human| {
+                human.age += interval;
+                let alive = human.is_alive(base_lifespan);
+                if !alive {
+                    deaths.push(human.id);
+                }
+                alive
+            });
+
+            // Starving colonies lose their oldest member every interval.
+            if colony.production.starving {
+                let oldest = colony
+                    .population
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|(_, human)| human.age)
+                    .map(|(index, _)| index);
+                if let Some(index) = oldest {
+                    deaths.push(colony.population.swap_remove(index).id);
+                }
+            }
+        }
+
+        let mut lost = Vec::new();
+        let pilots = self
+            .state
+            .scouts
+            .iter_mut()
+            .map(|(id, ship)| (*id, ShipKind::Scout, &mut ship.pilot, ship.position))
+            .chain(
+                self.state
+                    .miners
+                    .iter_mut()
+                    .map(|(id, ship)| (*id, ShipKind::Miner, &mut ship.pilot, ship.position)),
+            )
+            .chain(
+                self.state
+                    .haulers
+                    .iter_mut()
+                    .map(|(id, ship)| (*id, ShipKind::Hauler, &mut ship.pilot, ship.position)),
+            );
+        for (id, kind, pilot, position) in pilots {
+            pilot.age += interval;
+            if !pilot.is_alive(base_lifespan) {
+                deaths.push(pilot.id);
+                lost.push((id, kind, position.round().to_i64()));
+            }
+        }
+
+        for (id, kind, at) in lost {
+            match kind {
+                ShipKind::Scout => {
+                    self.state.scouts.remove(&id);
+                }
+                ShipKind::Miner => {
+                    self.state.miners.remove(&id);
+                }
+                ShipKind::Hauler => {
+                    self.state.haulers.remove(&id);
+                }
+            }
+            self.push_event(event::SimEvent::ShipLost { id, kind, at });
+        }
+
+        for id in deaths {
+            self.push_event(event::SimEvent::HumanDied(id));
+        }
+
+        let colonies: Vec<_> = self.state.colonies.keys().copied().collect();
+        for colony in colonies {
+            if let Some(id) = self.state.reproduce(colony) {
+                self.push_event(event::SimEvent::HumanBorn { id, colony });
+            }
+        }
     }
 
+    /// Discovers the asteroids in the area, skipping cells that were explored before.
     pub fn scout_area(&mut self, area: Area<f32>) {
-        let points: Vec<_> = area.to_i64().iter().collect();
+        let points: Vec<_> = area
+            .to_i64()
+            .iter()
+            .filter(|point| !self.state.explored.is_explored(*point))
+            .collect();
 
         let new_asteroids: Vec<_> = points
             .par_iter()
@@ -175,5 +1020,8 @@ impl Simulation {
         for point in new_asteroids {
             self.discover_asteroid(point);
         }
+        for point in points {
+            self.state.explored.mark(point);
+        }
     }
 }
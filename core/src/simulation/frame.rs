@@ -1,15 +1,30 @@
-use crate::math::eclipse::Eclipse;
 use crate::math::point::Point;
 use crate::math::rect::Rect;
 use crate::math::rgba::RGBA;
 use crate::math::size::Size;
+use crate::simulation::layer::{Layer, LayerSet};
+use crate::simulation::procedural::asteroid_shape::AsteroidShape;
+use crate::simulation::state::explored::{ExploredMap, CHUNK_SIZE};
 use crate::simulation::sync::snapshot::SimSnapshot;
 
+/// Number of accumulated asteroids at which an LOD pixel reaches about two thirds of full brightness.
+const LOD_SATURATION: f32 = 4.0;
+
+/// Pixel buffers of the visible world, one per layer, composited into `rgba` in layer order.
+/// Each pixel covers `cell_size` x `cell_size` world cells,
+/// more than one when zoomed out far enough to need the level-of-detail path.
 #[derive(Clone)]
 pub struct SimFrame {
     rgba: Vec<u8>,
+    layers: [Vec<u8>; Layer::ALL.len()],
+    drawn_layers: LayerSet,
     size: Size<u32>,
     visible_rect: Rect<f32>,
+    cell_size: i64,
+    /// Summed color channels and count of the asteroids in each pixel, only used for LOD.
+    accumulated: Vec<[u32; 4]>,
+    /// Explored cells in each pixel, reused between frames by the fog.
+    explored_counts: Vec<u32>,
     pub snapshot: SimSnapshot,
 }
 
@@ -18,7 +33,12 @@ impl SimFrame {
         Self {
             size: Size::new(width, height),
             visible_rect: Rect::default(),
+            cell_size: 1,
             rgba: vec![0u8; (width * height * 4) as usize],
+            layers: Default::default(),
+            drawn_layers: LayerSet::empty(),
+            accumulated: Vec::new(),
+            explored_counts: Vec::new(),
             snapshot: SimSnapshot::default(),
         }
     }
@@ -27,9 +47,12 @@ impl SimFrame {
         self.size
     }
 
+    /// Sizes the buffer to cover the visible rect at the current cell size.
     pub fn resize_to_visible_rect(&mut self) {
-        let width = (self.visible_rect.width().ceil() as u32 + 1).max(1);
-        let height = (self.visible_rect.height().ceil() as u32 + 1).max(1);
+        let min = self.pixel_of(self.visible_rect.min.floor().to_i64());
+        let max = self.pixel_of(self.visible_rect.max.floor().to_i64());
+        let width = (max.x - min.x + 1).max(1) as u32;
+        let height = (max.y - min.y + 1).max(1) as u32;
 
         if self.size.width != width || self.size.height != height {
             self.size = Size::new(width, height);
@@ -45,69 +68,241 @@ impl SimFrame {
         self.visible_rect
     }
 
+    pub fn set_cell_size(&mut self, cell_size: i64) {
+        self.cell_size = cell_size.max(1);
+    }
+
+    /// World cells per pixel along each axis.
+    pub fn cell_size(&self) -> i64 {
+        self.cell_size
+    }
+
+    pub fn is_lod(&self) -> bool {
+        self.cell_size > 1
+    }
+
+    /// World position of the top left corner of the first pixel.
+    pub fn origin(&self) -> Point<f32> {
+        let origin = self.pixel_of(self.visible_rect.min.floor().to_i64());
+        (origin * self.cell_size).to_f32()
+    }
+
+    /// Layers drawn into since the last clear.
+    pub fn drawn_layers(&self) -> LayerSet {
+        self.drawn_layers
+    }
+
     pub fn write_rgba(&self, dest: &mut [u8]) {
         let len = self.rgba.len().min(dest.len());
         dest[..len].copy_from_slice(&self.rgba[..len]);
     }
 
-    pub fn fill_cell(&mut self, world_pos: Point<f32>, color: RGBA) {
-        let width = self.size.width as i32;
-        let height = self.size.height as i32;
+    pub fn fill_cell(&mut self, layer: Layer, world_pos: Point<f32>, color: RGBA) {
+        if let Some(idx) = self.index_of(world_pos.floor().to_i64()) {
+            self.layer_mut(layer)[idx..idx + 4].copy_from_slice(&color);
+        }
+    }
 
-        let origin_x = self.visible_rect.min.x.floor() as i32;
-        let origin_y = self.visible_rect.min.y.floor() as i32;
+    /// Fills every cell whose center lies within the shape, shading craters.
+    /// The palette colors are dithered by their shares, which should sum to 1.
+    pub fn fill_asteroid(&mut self, layer: Layer, shape: &AsteroidShape, palette: &[(RGBA, f32)]) {
+        let Some(visible_part) = self.visible_rect.intersect(&shape.bounds()) else {
+            return;
+        };
 
-        let buf_x = world_pos.x.floor() as i32 - origin_x;
-        let buf_y = world_pos.y.floor() as i32 - origin_y;
+        for world_point in visible_part.floor().to_i64().iter() {
+            let Some(idx) = self.index_of(world_point) else {
+                continue;
+            };
 
-        if buf_x >= 0 && buf_x < width && buf_y >= 0 && buf_y < height {
-            let idx = ((buf_y * width + buf_x) * 4) as usize;
-            self.rgba[idx..idx + 4].copy_from_slice(&color);
+            let cell_center = world_point.to_f32() + Point::new(0.5, 0.5);
+            if let Some(shade) = shape.shade_at(cell_center) {
+                let color = dither(palette, world_point);
+                self.layer_mut(layer)[idx..idx + 4].copy_from_slice(&color.shaded(shade));
+            }
         }
     }
 
-    pub fn fill_ellipse(&mut self, eclipse: Eclipse<f32>, color: RGBA) {
+    /// Fills every pixel the rect touches, so small rects stay visible when zoomed out.
+    pub fn fill_rect(&mut self, layer: Layer, rect: Rect<f32>, color: RGBA) {
+        let Some(visible_part) = self.visible_rect.intersect(&rect) else {
+            return;
+        };
+
+        let origin = self.pixel_of(self.visible_rect.min.floor().to_i64());
+        let min = self.pixel_of(visible_part.min.floor().to_i64()) - origin;
+        let max = self.pixel_of(visible_part.max.floor().to_i64()) - origin;
         let width = self.size.width as i64;
         let height = self.size.height as i64;
 
-        let origin_x = self.visible_rect.min.x.floor() as i64;
-        let origin_y = self.visible_rect.min.y.floor() as i64;
+        let buffer = self.layer_mut(layer);
+        for y in min.y.max(0)..=max.y.min(height - 1) {
+            for x in min.x.max(0)..=max.x.min(width - 1) {
+                let idx = ((y * width + x) * 4) as usize;
+                buffer[idx..idx + 4].copy_from_slice(&color);
+            }
+        }
+    }
+
+    /// Adds an asteroid to the LOD pixel covering it, see [`Self::resolve_accumulated`].
+    pub fn accumulate(&mut self, world_point: Point<i64>, color: RGBA) {
+        let Some(idx) = self.index_of(world_point) else {
+            return;
+        };
 
-        for world_point in eclipse.to_i64().iter() {
-            let buf_x = world_point.x - origin_x;
-            let buf_y = world_point.y - origin_y;
+        let pixel_count = (self.size.width * self.size.height) as usize;
+        if self.accumulated.len() != pixel_count {
+            self.accumulated = vec![[0; 4]; pixel_count];
+        }
 
-            if buf_x >= 0 && buf_x < width && buf_y >= 0 && buf_y < height {
-                let idx = ((buf_y * width + buf_x) * 4) as usize;
-                self.rgba[idx..idx + 4].copy_from_slice(&color);
+        let pixel = &mut self.accumulated[idx / 4];
+        pixel[0] += color.r() as u32;
+        pixel[1] += color.g() as u32;
+        pixel[2] += color.b() as u32;
+        pixel[3] += 1;
+    }
+
+    /// Draws the average color of the accumulated asteroids in each pixel,
+    /// brighter the more asteroids it covers.
+    pub fn resolve_accumulated(&mut self, layer: Layer) {
+        let mut accumulated = std::mem::take(&mut self.accumulated);
+        let buffer = self.layer_mut(layer);
+        for (idx, pixel) in accumulated.iter_mut().enumerate() {
+            let [r, g, b, count] = *pixel;
+            if count == 0 {
+                continue;
             }
+
+            let average = RGBA::rgb((r / count) as u8, (g / count) as u8, (b / count) as u8);
+            let shade = 1.0 - 0.6 * (-(count as f32) / LOD_SATURATION).exp();
+            buffer[idx * 4..idx * 4 + 4].copy_from_slice(&average.shaded(shade));
+            *pixel = [0; 4];
         }
+        self.accumulated = accumulated;
     }
 
-    pub fn fill_rect(&mut self, rect: Rect<f32>, color: RGBA) {
-        if let Some(visible_part) = self.visible_rect.intersect(&rect) {
-            let origin_x = self.visible_rect.min.x.floor() as i64;
-            let origin_y = self.visible_rect.min.y.floor() as i64;
-            let width = self.size.width as i64;
+    /// Covers the frame in `color`, fading it out by the explored share of each pixel's cells.
+    pub fn fill_fog(&mut self, layer: Layer, explored: &ExploredMap, color: RGBA) {
+        let mut counts = std::mem::take(&mut self.explored_counts);
+        counts.clear();
+        counts.resize((self.size.width * self.size.height) as usize, 0);
 
-            let draw_rect = visible_part.to_i64();
-            for world_point in draw_rect.iter() {
-                let buf_x = world_point.x - origin_x;
-                let buf_y = world_point.y - origin_y;
+        // Bits of a chunk row that fall into the same pixel are counted together.
+        let segment = self.cell_size.min(CHUNK_SIZE);
+        let mask = if segment == CHUNK_SIZE {
+            u64::MAX
+        } else {
+            (1 << segment) - 1
+        };
 
-                if buf_x >= 0 && buf_x < width {
-                    let idx = ((buf_y * width + buf_x) * 4) as usize;
-                    if idx + 4 <= self.rgba.len() {
-                        self.rgba[idx..idx + 4].copy_from_slice(&color);
+        let rect = self.visible_rect.floor().to_i64();
+        for (chunk, bits) in explored.chunks_in_rect(rect) {
+            for (row, row_bits) in bits.iter().enumerate() {
+                if *row_bits == 0 {
+                    continue;
+                }
+
+                let y = chunk.y * CHUNK_SIZE + row as i64;
+                for start in (0..CHUNK_SIZE).step_by(segment as usize) {
+                    let count = (row_bits >> start & mask).count_ones();
+                    let point = Point::new(chunk.x * CHUNK_SIZE + start, y);
+                    if count > 0
+                        && let Some(idx) = self.index_of(point)
+                    {
+                        counts[idx / 4] += count;
                     }
                 }
             }
         }
+
+        let cells_per_pixel = (self.cell_size * self.cell_size) as f32;
+        let buffer = self.layer_mut(layer);
+        for (pixel, count) in buffer.chunks_exact_mut(4).zip(&counts) {
+            let unexplored = 1.0 - (*count as f32 / cells_per_pixel).min(1.0);
+            let alpha = (color.a() as f32 * unexplored) as u8;
+            pixel.copy_from_slice(&RGBA::new(color.r(), color.g(), color.b(), alpha));
+        }
+        self.explored_counts = counts;
     }
 
     pub fn clear(&mut self) {
+        for layer in self.drawn_layers.iter() {
+            self.layers[layer.index()].fill(0);
+        }
+        self.drawn_layers = LayerSet::empty();
         self.rgba.fill(0);
     }
+
+    /// Blends the drawn layers on top of each other into the output buffer.
+    pub fn composite(&mut self) {
+        self.rgba.fill(0);
+        for layer in self.drawn_layers.iter() {
+            let buffer = &self.layers[layer.index()];
+            for (dest, src) in self.rgba.chunks_exact_mut(4).zip(buffer.chunks_exact(4)) {
+                match src[3] {
+                    0 => {}
+                    255 => dest.copy_from_slice(src),
+                    alpha => {
+                        let alpha = alpha as u32;
+                        for channel in 0..3 {
+                            dest[channel] = ((src[channel] as u32 * alpha
+                                + dest[channel] as u32 * (255 - alpha))
+                                / 255) as u8;
+                        }
+                        dest[3] = (alpha + dest[3] as u32 * (255 - alpha) / 255) as u8;
+                    }
+                }
+            }
+        }
+    }
+
+    /// The layer's buffer sized to the frame, marking it as drawn.
+    fn layer_mut(&mut self, layer: Layer) -> &mut Vec<u8> {
+        self.drawn_layers.insert(layer);
+        let buffer = &mut self.layers[layer.index()];
+        buffer.resize(self.rgba.len(), 0);
+        buffer
+    }
+
+    fn pixel_of(&self, world_point: Point<i64>) -> Point<i64> {
+        Point::new(
+            world_point.x.div_euclid(self.cell_size),
+            world_point.y.div_euclid(self.cell_size),
+        )
+    }
+
+    /// Byte index of the pixel covering the world cell, if it is inside the buffer.
+    fn index_of(&self, world_point: Point<i64>) -> Option<usize> {
+        let origin = self.pixel_of(self.visible_rect.min.floor().to_i64());
+        let pixel = self.pixel_of(world_point) - origin;
+        let width = self.size.width as i64;
+        let height = self.size.height as i64;
+
+        if pixel.x >= 0 && pixel.x < width && pixel.y >= 0 && pixel.y < height {
+            Some(((pixel.y * width + pixel.x) * 4) as usize)
+        } else {
+            None
+        }
+    }
+}
+
+/// 4x4 ordered dithering matrix, spreading thresholds evenly over neighbouring cells.
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Picks the palette color covering the cell's threshold, so each color fills about its share.
+fn dither(palette: &[(RGBA, f32)], point: Point<i64>) -> RGBA {
+    let threshold =
+        (BAYER_4X4[point.y.rem_euclid(4) as usize][point.x.rem_euclid(4) as usize] as f32 + 0.5)
+            / 16.0;
+
+    let mut cumulative = 0.0;
+    for (color, share) in palette {
+        cumulative += share;
+        if threshold < cumulative {
+            return *color;
+        }
+    }
+    palette.last().map_or(RGBA::white(), |(color, _)| *color)
 }
 
 impl Default for SimFrame {
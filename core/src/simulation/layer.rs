@@ -0,0 +1,93 @@
+use std::fmt::Display;
+
+/// Frame layers in compositing order, later layers are drawn on top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Layer {
+    Asteroids,
+    Colonies,
+    Ships,
+    Fog,
+    Debug,
+}
+
+impl Layer {
+    pub const ALL: [Layer; 5] = [
+        Layer::Asteroids,
+        Layer::Colonies,
+        Layer::Ships,
+        Layer::Fog,
+        Layer::Debug,
+    ];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl Display for Layer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Layer::Asteroids => write!(f, "Asteroids"),
+            Layer::Colonies => write!(f, "Colonies"),
+            Layer::Ships => write!(f, "Ships"),
+            Layer::Fog => write!(f, "Fog"),
+            Layer::Debug => write!(f, "Debug"),
+        }
+    }
+}
+
+/// A set of layers stored as bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayerSet(u8);
+
+impl LayerSet {
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub fn contains(self, layer: Layer) -> bool {
+        self.0 & (1 << layer.index()) != 0
+    }
+
+    pub fn insert(&mut self, layer: Layer) {
+        self.0 |= 1 << layer.index();
+    }
+
+    pub fn remove(&mut self, layer: Layer) {
+        self.0 &= !(1 << layer.index());
+    }
+
+    pub fn set(&mut self, layer: Layer, included: bool) {
+        if included {
+            self.insert(layer);
+        } else {
+            self.remove(layer);
+        }
+    }
+
+    pub fn iter(self) -> impl Iterator<Item = Layer> {
+        Layer::ALL
+            .into_iter()
+            .filter(move |layer| self.contains(*layer))
+    }
+}
+
+/// Everything but the debug layer.
+impl Default for LayerSet {
+    fn default() -> Self {
+        Layer::ALL
+            .into_iter()
+            .filter(|layer| *layer != Layer::Debug)
+            .collect()
+    }
+}
+
+impl FromIterator<Layer> for LayerSet {
+    fn from_iter<T: IntoIterator<Item = Layer>>(iter: T) -> Self {
+        let mut set = Self::empty();
+        for layer in iter {
+            set.insert(layer);
+        }
+        set
+    }
+}
@@ -1,2 +1,4 @@
 pub mod asteroid_shape;
 pub mod hash;
+pub mod noise;
+pub mod structure;
@@ -1,8 +1,130 @@
 use crate::math::eclipse::Eclipse;
 use crate::math::point::Point;
+use crate::math::rect::Rect;
+use crate::simulation::procedural::hash::{ProcHash, ProcHashDomain};
+use std::f32::consts::TAU;
+
+/// Number of radius samples around the outline, interpolated in between.
+const OUTLINE_SAMPLES: usize = 12;
+const MAX_CRATERS: usize = 3;
+/// How far the outline may dent inwards, relative to the base radius.
+const ROUGHNESS: f32 = 0.35;
+const CRATER_SHADE: f32 = 0.55;
+const CRATER_RIM_SHADE: f32 = 1.25;
 
 pub fn asteroid_shape_eclipse(seed: u64, center: Point<f32>, scale: f32) -> Eclipse<f32> {
     let rx = scale * (0.7 + (seed >> 56) as f32 * (0.3 / 255.0));
     let ry = scale * (0.7 + ((seed >> 48) & 0xFF) as f32 * (0.3 / 255.0));
     Eclipse::new(center, rx, ry)
 }
+
+#[derive(Debug, Clone, Copy)]
+struct Crater {
+    center: Point<f32>,
+    radius: f32,
+}
+
+/// A rotated, lumpy outline with a few craters, fully derived from the asteroid shape seed.
+#[derive(Debug, Clone)]
+pub struct AsteroidShape {
+    center: Point<f32>,
+    rotation: f32,
+    rx: f32,
+    ry: f32,
+    outline: [f32; OUTLINE_SAMPLES],
+    craters: [Crater; MAX_CRATERS],
+    crater_count: usize,
+}
+
+impl AsteroidShape {
+    /// The shape of the asteroid in the cell at `point`, `scale` being its approximate radius.
+    pub fn new(seed: u64, point: Point<i64>, scale: f32) -> Self {
+        let roll = |salt: u64| {
+            ProcHash::from_id(seed, salt, 0, ProcHashDomain::AsteroidShape).normalized() as f32
+        };
+
+        let center = point.to_f32() + Point::new(0.5, 0.5);
+        let eclipse = asteroid_shape_eclipse(seed, center, scale);
+        let outline = std::array::from_fn(|i| 1.0 - ROUGHNESS * roll(i as u64));
+
+        // Craters only show up once there are enough cells to carve them out of.
+        let crater_count = if scale < 3.0 {
+            0
+        } else {
+            (roll(100) * (MAX_CRATERS + 1) as f32) as usize
+        };
+        let craters = std::array::from_fn(|i| {
+            let salt = 101 + i as u64 * 3;
+            let offset = Point::from_angle(roll(salt) * TAU) * roll(salt + 1) * 0.6;
+            Crater {
+                center: Point::new(offset.x * eclipse.rx, offset.y * eclipse.ry),
+                radius: scale * (0.15 + 0.2 * roll(salt + 2)),
+            }
+        });
+
+        Self {
+            center,
+            rotation: roll(99) * TAU,
+            rx: eclipse.rx,
+            ry: eclipse.ry,
+            outline,
+            craters,
+            crater_count,
+        }
+    }
+
+    /// World rect covering every cell the shape can touch.
+    pub fn bounds(&self) -> Rect<f32> {
+        let radius = self.rx.max(self.ry);
+        Rect::new(
+            Point::new(self.center.x - radius, self.center.y - radius),
+            Point::new(self.center.x + radius, self.center.y + radius),
+        )
+    }
+
+    /// Brightness of the surface at `point`, `None` outside of the outline.
+    pub fn shade_at(&self, point: Point<f32>) -> Option<f32> {
+        let offset = point - self.center;
+        // Rotate into the shape's local frame.
+        let (sin, cos) = (-self.rotation).sin_cos();
+        let local = Point::new(
+            offset.x * cos - offset.y * sin,
+            offset.x * sin + offset.y * cos,
+        );
+
+        let distance = local.length();
+        let angle = local.y.atan2(local.x);
+        if distance > self.radius_at(angle) {
+            // The center cell is always solid, so even tiny asteroids stay visible.
+            let is_center = offset.x.abs() < 0.5 && offset.y.abs() < 0.5;
+            return is_center.then_some(1.0);
+        }
+
+        let mut shade = 1.0;
+        for crater in &self.craters[..self.crater_count] {
+            let t = local.distance(crater.center) / crater.radius;
+            if t < 0.75 {
+                shade = CRATER_SHADE;
+            } else if t < 1.0 && shade > CRATER_SHADE {
+                shade = CRATER_RIM_SHADE;
+            }
+        }
+        Some(shade)
+    }
+
+    /// Outline radius in the local frame, an ellipse dented by interpolated noise samples.
+    fn radius_at(&self, angle: f32) -> f32 {
+        let (sin, cos) = angle.sin_cos();
+        let ellipse =
+            self.rx * self.ry / ((self.ry * cos).powi(2) + (self.rx * sin).powi(2)).sqrt();
+
+        let position = angle.rem_euclid(TAU) / TAU * OUTLINE_SAMPLES as f32;
+        let index = position as usize % OUTLINE_SAMPLES;
+        let next = (index + 1) % OUTLINE_SAMPLES;
+        let t = position.fract();
+        let t = t * t * (3.0 - 2.0 * t);
+        let noise = self.outline[index] + (self.outline[next] - self.outline[index]) * t;
+
+        ellipse * noise
+    }
+}
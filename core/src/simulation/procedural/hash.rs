@@ -15,6 +15,14 @@ impl ProcHash {
         Self(hasher.finish())
     }
 
+    pub fn from_id(seed: u64, id: u64, salt: u64, domain: ProcHashDomain) -> Self {
+        let mut hasher = RapidHasher::new(seed);
+        hasher.write_u64(id);
+        hasher.write_u64(salt);
+        hasher.write_u64(domain as u64);
+        Self(hasher.finish())
+    }
+
     #[inline]
     /// Uniform distribution in [0, n]
     pub fn uniform_n(&self, n: u64) -> u64 {
@@ -39,4 +47,18 @@ pub enum ProcHashDomain {
     AsteroidResourceType = 2,
     AsteroidResourceAmount = 3,
     AsteroidShape = 4,
+    ScoutHeading = 5,
+    HumanGenome = 6,
+    HumanAge = 7,
+    ParentSelection = 8,
+    GeneCrossover = 9,
+    GeneMutation = 10,
+    DensityNoise = 11,
+    ResourceNoise = 12,
+    // 13 was the gold noise, replaced by per-resource seeds of `ResourceNoise`. It stays unused,
+    // renumbering would reshape saved worlds and reusing it would collide with old gold hashes.
+    ClusterSite = 14,
+    StarSite = 15,
+    SiteProperty = 16,
+    AsteroidComposition = 17,
 }
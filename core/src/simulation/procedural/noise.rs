@@ -0,0 +1,48 @@
+use crate::math::point::Point;
+use crate::simulation::procedural::hash::{ProcHash, ProcHashDomain};
+
+/// Seeded 2D value noise in [0, 1]: hashed lattice values blended with a smoothstep.
+/// `scale` is the distance between lattice points in cells.
+pub fn value_noise(seed: u64, point: Point<i64>, scale: f32, domain: ProcHashDomain) -> f64 {
+    let x = point.x as f64 / scale as f64;
+    let y = point.y as f64 / scale as f64;
+    let (x0, y0) = (x.floor(), y.floor());
+    let (tx, ty) = (smoothstep(x - x0), smoothstep(y - y0));
+    let (x0, y0) = (x0 as i64, y0 as i64);
+
+    let lattice = |x, y| ProcHash::from_point_i64(seed, Point::new(x, y), domain).normalized();
+    let top = lerp(lattice(x0, y0), lattice(x0 + 1, y0), tx);
+    let bottom = lerp(lattice(x0, y0 + 1), lattice(x0 + 1, y0 + 1), tx);
+    lerp(top, bottom, ty)
+}
+
+/// Sum of `octaves` layers of value noise at halving scales and amplitudes, in [0, 1].
+pub fn fractal_noise(
+    seed: u64,
+    point: Point<i64>,
+    scale: f32,
+    octaves: u32,
+    domain: ProcHashDomain,
+) -> f64 {
+    let mut total = 0.0;
+    let mut amplitude = 1.0;
+    let mut max_total = 0.0;
+    let mut scale = scale;
+
+    for octave in 0..octaves.max(1) {
+        let octave_seed = seed.wrapping_add(octave as u64);
+        total += value_noise(octave_seed, point, scale, domain) * amplitude;
+        max_total += amplitude;
+        amplitude *= 0.5;
+        scale = (scale * 0.5).max(1.0);
+    }
+    total / max_total
+}
+
+fn smoothstep(t: f64) -> f64 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
@@ -0,0 +1,118 @@
+use crate::math::point::Point;
+use crate::simulation::procedural::hash::{ProcHash, ProcHashDomain};
+
+/// Sparse round asteroid clusters, at most one centered in each grid cell of `spacing` cells.
+#[derive(Debug, Clone, Copy)]
+pub struct ClusterField {
+    pub spacing: f32,
+    pub chance: f64,
+    pub min_radius: f32,
+    pub max_radius: f32,
+    /// Asteroid probability per cell at a cluster center, falling off towards its edge.
+    pub density: f64,
+}
+
+impl ClusterField {
+    pub fn density_at(&self, seed: u64, point: Point<i64>) -> f64 {
+        if self.chance <= 0.0 || self.density <= 0.0 {
+            return 0.0;
+        }
+
+        nearby_sites(seed, point, self.spacing, ProcHashDomain::ClusterSite)
+            .filter(|site| site.roll(0) < self.chance)
+            .map(|site| {
+                let radius = lerp(self.min_radius, self.max_radius, site.roll(1));
+                let distance = site.center.distance(point.to_f32());
+                self.density * falloff(distance / radius)
+            })
+            .sum()
+    }
+}
+
+/// Rings of asteroids around sparse hashed star points, at most one star per grid cell.
+#[derive(Debug, Clone, Copy)]
+pub struct BeltField {
+    pub spacing: f32,
+    pub chance: f64,
+    pub min_radius: f32,
+    pub max_radius: f32,
+    pub width: f32,
+    /// Asteroid probability per cell along the middle of a belt.
+    pub density: f64,
+}
+
+impl BeltField {
+    pub fn density_at(&self, seed: u64, point: Point<i64>) -> f64 {
+        if self.chance <= 0.0 || self.density <= 0.0 {
+            return 0.0;
+        }
+
+        nearby_sites(seed, point, self.spacing, ProcHashDomain::StarSite)
+            .filter(|site| site.roll(0) < self.chance)
+            .map(|site| {
+                let radius = lerp(self.min_radius, self.max_radius, site.roll(1));
+                let distance = site.center.distance(point.to_f32());
+                let offset = (distance - radius).abs();
+                self.density * falloff(offset / (self.width * 0.5))
+            })
+            .sum()
+    }
+}
+
+/// A hashed point placed somewhere inside its grid cell.
+struct Site {
+    center: Point<f32>,
+    hash: ProcHash,
+}
+
+impl Site {
+    /// Independent uniform value in [0, 1) for each salt.
+    fn roll(&self, salt: u64) -> f64 {
+        ProcHash::from_id(self.hash.raw(), salt, 0, ProcHashDomain::SiteProperty).normalized()
+    }
+}
+
+/// The sites of the 3x3 grid cells around the point. Structures must fit within one cell
+/// spacing of their site so that nothing outside this neighbourhood can reach the point.
+fn nearby_sites(
+    seed: u64,
+    point: Point<i64>,
+    spacing: f32,
+    domain: ProcHashDomain,
+) -> impl Iterator<Item = Site> {
+    let cell = Point::new(
+        (point.x as f32 / spacing).floor() as i64,
+        (point.y as f32 / spacing).floor() as i64,
+    );
+
+    (-1..=1).flat_map(move |dy| {
+        (-1..=1).map(move |dx| {
+            let cell = Point::new(cell.x + dx, cell.y + dy);
+            let hash = ProcHash::from_point_i64(seed, cell, domain);
+            let site = Site {
+                center: cell.to_f32() * spacing,
+                hash,
+            };
+            let offset = Point::new(site.roll(2) as f32, site.roll(3) as f32) * spacing;
+            Site {
+                center: site.center + offset,
+                ..site
+            }
+        })
+    })
+}
+
+/// Smooth falloff from 1 at the center to 0 at `t >= 1`.
+fn falloff(t: f32) -> f64 {
+    let t = t as f64;
+    if t >= 1.0 {
+        0.0
+    } else {
+        let s = 1.0 - t * t;
+        s * s
+    }
+}
+
+fn lerp(a: f32, b: f32, t: f64) -> f32 {
+    a + (b - a) * t as f32
+}
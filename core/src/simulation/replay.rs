@@ -0,0 +1,103 @@
+use crate::simulation::state::persistence::{migration, read_section, PersistenceError, SaveFile};
+use crate::simulation::state::SimState;
+use crate::simulation::sync::command::SimCommand;
+use crate::simulation::Simulation;
+use std::collections::VecDeque;
+use std::path::Path;
+
+const SECTION_COMMANDS: &str = "commands";
+
+/// A command that was handled after the given tick completed.
+#[derive(Debug, Clone)]
+pub struct RecordedCommand {
+    pub tick: u64,
+    pub command: SimCommand,
+}
+
+/// The state a recording started from and every replayable command handled since.
+/// On disk this is a regular save file with an additional commands section.
+#[derive(Debug, Clone)]
+pub struct Replay {
+    initial_state: SaveFile,
+    pub commands: Vec<RecordedCommand>,
+}
+
+impl Replay {
+    pub fn new(state: &SimState) -> Self {
+        Self {
+            initial_state: state.to_save_file(),
+            commands: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, tick: u64, command: &SimCommand) {
+        if command.is_replayable() {
+            self.commands.push(RecordedCommand {
+                tick,
+                command: command.clone(),
+            });
+        }
+    }
+
+    pub fn save_to(&self, path: impl AsRef<Path>) -> Result<(), PersistenceError> {
+        std::fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+
+    pub fn load_from(path: impl AsRef<Path>) -> Result<Self, PersistenceError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut save = self.initial_state.clone();
+        save.set_section(SECTION_COMMANDS, &self.commands);
+        save.to_bytes()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PersistenceError> {
+        let mut save = SaveFile::from_bytes(bytes)?;
+        migration::migrate(&mut save)?;
+        let commands = read_section(&save, SECTION_COMMANDS)?;
+        save.sections.remove(SECTION_COMMANDS);
+
+        Ok(Self {
+            initial_state: save,
+            commands,
+        })
+    }
+
+    /// Decodes the initial state and queues the commands to be fed back into it.
+    pub fn into_parts(self) -> Result<(SimState, CommandSchedule), PersistenceError> {
+        let state = SimState::from_save_file(&self.initial_state)?;
+        let schedule = CommandSchedule {
+            commands: self.commands.into(),
+        };
+        Ok((state, schedule))
+    }
+}
+
+/// Recorded commands waiting to be handled once the simulation reaches their tick.
+#[derive(Debug, Default)]
+pub struct CommandSchedule {
+    commands: VecDeque<RecordedCommand>,
+}
+
+impl CommandSchedule {
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Handles all commands that are due at the current tick.
+    /// Returns true if this applied the last scheduled command.
+    pub fn apply_due(&mut self, simulation: &mut Simulation) -> bool {
+        let mut applied = false;
+        while let Some(recorded) = self.commands.front()
+            && recorded.tick <= simulation.state().tick()
+        {
+            let recorded = self.commands.pop_front().unwrap();
+            simulation.handle_command(recorded.command);
+            applied = true;
+        }
+        applied && self.commands.is_empty()
+    }
+}
@@ -3,6 +3,7 @@ use crate::simulation::sync::command::SimCommand;
 use crate::simulation::sync::event::SimEvent;
 
 pub mod local;
+pub mod replay;
 
 pub trait SimSource: Send {
     fn is_alive(&self) -> bool;
@@ -1,9 +1,8 @@
 use crate::math::ema::EMA;
-use crate::math::point::Point;
 use crate::simulation::frame::SimFrame;
-use crate::simulation::source::local::context::LocalSimContext;
+use crate::simulation::replay::CommandSchedule;
+use crate::simulation::source::local::context::{LocalSimContext, TpsCounter};
 use crate::simulation::source::SimSource;
-use crate::simulation::state::colony::Colony;
 use crate::simulation::state::SimState;
 use crate::simulation::sync::command::SimCommand;
 use crate::simulation::sync::event::SimEvent;
@@ -22,22 +21,27 @@ pub struct LocalSim {
 
 impl LocalSim {
     pub fn spawn(state: SimState) -> Self {
+        Self::spawn_with_schedule(state, CommandSchedule::default())
+    }
+
+    /// Spawns a simulation that handles the scheduled commands once it reaches their ticks.
+    pub fn spawn_with_schedule(state: SimState, schedule: CommandSchedule) -> Self {
         let (command_tx, command_rx) = mpsc::channel();
         let (event_tx, event_rx) = mpsc::channel();
         let (frame_writer, frame_reader) = TripleBuffer::new(&SimFrame::default()).split();
 
         let thread = std::thread::spawn(move || {
-            let mut sim = Simulation::new(state);
-            sim.state
-                .colonies
-                .insert(Point::new(1000, 700), Colony::default());
+            let tps_counter = TpsCounter::new(state.tick());
+            let sim = Simulation::new(state);
             let context = LocalSimContext {
                 simulation: sim,
                 command_rx,
                 event_tx,
                 frame_writer,
+                schedule,
                 avg_frame_secs: EMA::default(),
                 avg_tick_secs: EMA::default(),
+                tps_counter,
             };
             context.run();
         });
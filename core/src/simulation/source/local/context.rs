@@ -1,5 +1,6 @@
 use crate::math::ema::EMA;
 use crate::simulation::frame::SimFrame;
+use crate::simulation::replay::CommandSchedule;
 use crate::simulation::sync::command::SimCommand;
 use crate::simulation::sync::event::SimEvent;
 use crate::simulation::Simulation;
@@ -11,8 +12,40 @@ pub struct LocalSimContext {
     pub command_rx: mpsc::Receiver<SimCommand>,
     pub event_tx: mpsc::Sender<SimEvent>,
     pub frame_writer: triple_buffer::Input<SimFrame>,
+    pub schedule: CommandSchedule,
     pub avg_frame_secs: EMA,
     pub avg_tick_secs: EMA,
+    pub tps_counter: TpsCounter,
+}
+
+/// Measures the achieved tick rate over windows of wall-clock time.
+pub struct TpsCounter {
+    window_start: Instant,
+    window_start_tick: u64,
+    tps: f64,
+}
+
+impl TpsCounter {
+    const WINDOW: Duration = Duration::from_millis(500);
+
+    pub fn new(tick: u64) -> Self {
+        Self {
+            window_start: Instant::now(),
+            window_start_tick: tick,
+            tps: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, tick: u64) -> f64 {
+        let elapsed = self.window_start.elapsed();
+        if elapsed >= Self::WINDOW {
+            let ticks = tick.saturating_sub(self.window_start_tick);
+            self.tps = ticks as f64 / elapsed.as_secs_f64();
+            self.window_start = Instant::now();
+            self.window_start_tick = tick;
+        }
+        self.tps
+    }
 }
 
 impl LocalSimContext {
@@ -31,15 +64,33 @@ impl LocalSimContext {
                 break;
             }
 
-            let tick_interval = self.simulation.settings().interval_tps();
-            while now.duration_since(last_tick) >= tick_interval {
-                let start = Instant::now();
-                self.simulation.tick(false);
-                self.avg_tick_secs.update(start.elapsed().as_secs_f64());
-                last_tick += tick_interval;
+            let render_interval = self.simulation.settings().interval_fps();
+            let tick_interval = self.simulation.tick_interval();
+            match tick_interval {
+                Some(tick_interval) => {
+                    while now.duration_since(last_tick) >= tick_interval {
+                        self.tick();
+                        last_tick += tick_interval;
+
+                        // Drop the backlog rather than starving frames when ticks can't keep up.
+                        if now.elapsed() >= render_interval {
+                            last_tick = Instant::now();
+                            break;
+                        }
+                    }
+                }
+                None => {
+                    // Unbounded: tick until the next frame is due.
+                    let next_render = last_render + render_interval;
+                    while self.simulation.is_running() && Instant::now() < next_render {
+                        self.tick();
+                    }
+                    last_tick = Instant::now();
+                }
             }
 
-            let render_interval = self.simulation.settings().interval_fps();
+            self.forward_events();
+
             if now.duration_since(last_render) >= render_interval {
                 let start = Instant::now();
                 self.render_frame();
@@ -47,9 +98,12 @@ impl LocalSimContext {
                 last_render = now;
             }
 
-            let next_tick = last_tick + tick_interval;
             let next_render = last_render + render_interval;
-            let next_event = next_tick.min(next_render);
+            let next_event = match tick_interval {
+                Some(tick_interval) => (last_tick + tick_interval).min(next_render),
+                None if self.simulation.is_running() => continue,
+                None => next_render,
+            };
 
             if let Some(wait) = next_event.checked_duration_since(Instant::now()) {
                 spin_sleep::sleep(wait);
@@ -57,11 +111,31 @@ impl LocalSimContext {
         }
     }
 
+    fn tick(&mut self) {
+        self.apply_schedule();
+        let start = Instant::now();
+        self.simulation.tick(false);
+        self.avg_tick_secs.update(start.elapsed().as_secs_f64());
+    }
+
+    fn apply_schedule(&mut self) {
+        if self.schedule.apply_due(&mut self.simulation) {
+            let _ = self.event_tx.send(SimEvent::ReplayFinished);
+        }
+    }
+
+    fn forward_events(&mut self) {
+        while let Some(event) = self.simulation.poll_event() {
+            let _ = self.event_tx.send(event);
+        }
+    }
+
     pub fn render_frame(&mut self) {
         let frame = self.frame_writer.input_buffer_mut();
         self.simulation.update_frame(frame);
         frame.snapshot.avg_frame = Duration::from_secs_f64(self.avg_frame_secs.get());
         frame.snapshot.avg_tick = Duration::from_secs_f64(self.avg_tick_secs.get());
+        frame.snapshot.effective_tps = self.tps_counter.update(self.simulation.state().tick());
         self.frame_writer.publish();
     }
 }
@@ -0,0 +1,45 @@
+use crate::simulation::frame::SimFrame;
+use crate::simulation::replay::Replay;
+use crate::simulation::source::local::LocalSim;
+use crate::simulation::source::SimSource;
+use crate::simulation::state::persistence::PersistenceError;
+use crate::simulation::sync::command::SimCommand;
+use crate::simulation::sync::event::SimEvent;
+use std::path::Path;
+
+/// Runs a fresh simulation from a recording's initial state and feeds the recorded
+/// commands back in at their ticks. Afterwards it keeps running like a `LocalSim`.
+pub struct ReplaySim {
+    local: LocalSim,
+}
+
+impl ReplaySim {
+    pub fn spawn(replay: Replay) -> Result<Self, PersistenceError> {
+        let (state, schedule) = replay.into_parts()?;
+        Ok(Self {
+            local: LocalSim::spawn_with_schedule(state, schedule),
+        })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PersistenceError> {
+        Self::spawn(Replay::load_from(path)?)
+    }
+}
+
+impl SimSource for ReplaySim {
+    fn is_alive(&self) -> bool {
+        self.local.is_alive()
+    }
+
+    fn send_command(&self, command: SimCommand) {
+        self.local.send_command(command);
+    }
+
+    fn poll_event(&self) -> Option<SimEvent> {
+        self.local.poll_event()
+    }
+
+    fn read_frame(&mut self) -> &SimFrame {
+        self.local.read_frame()
+    }
+}
@@ -1,30 +1,90 @@
 use crate::math::point::Point;
 use crate::simulation::procedural::hash::{ProcHash, ProcHashDomain};
+use crate::simulation::procedural::noise::fractal_noise;
 use crate::simulation::state::colony::Colony;
-use std::collections::{HashMap, HashSet};
+use crate::simulation::state::composition::Composition;
+use crate::simulation::state::depot::Depot;
+use crate::simulation::state::hauler::{Hauler, HaulerTask};
+use crate::simulation::state::human::{Gene, Genome, Human, HumanId, LineageId};
+use crate::simulation::state::logistics::{Stockpile, TransportRequest};
+use crate::simulation::state::miner::Miner;
+use crate::simulation::state::production::Product;
+use crate::simulation::state::resource_bag::ResourceBag;
+use crate::simulation::state::scout::Scout;
+use crate::simulation::state::ship::ShipId;
+use crate::simulation::state::spatial_index::SpatialIndex;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
 
 pub mod colony;
+pub mod composition;
+pub mod depot;
+pub mod explored;
+pub mod hauler;
+pub mod human;
+pub mod logistics;
+pub mod miner;
+pub mod persistence;
+pub mod production;
 pub mod resource;
-mod resource_bag;
+pub mod resource_bag;
+pub mod scout;
 pub mod settings;
+pub mod ship;
+pub mod spatial_index;
+
+const MAX_DENSITY_FACTOR: f64 = 3.0;
+/// The primary resource of an asteroid makes up at least this share of it.
+const MIN_PRIMARY_SHARE: f64 = 0.5;
 
 #[derive(Debug, Clone)]
 pub struct SimState {
     seed: u64,
+    tick: u64,
     pub settings: settings::SimulationSettings,
-    pub discovered_asteroids: HashMap<Point<i64>, f32>,
-    pub depleted_asteroids: HashSet<Point<i64>>,
-    pub colonies: HashMap<Point<i64>, Colony>,
+    pub resource_types: resource::ResourceRegistry,
+    pub discovered_asteroids: BTreeMap<Point<i64>, f32>,
+    pub depleted_asteroids: BTreeSet<Point<i64>>,
+    /// Cells scouts have scanned, whether or not they held an asteroid.
+    pub explored: explored::ExploredMap,
+    pub colonies: BTreeMap<Point<i64>, Colony>,
+    pub scouts: BTreeMap<ShipId, Scout>,
+    pub miners: BTreeMap<ShipId, Miner>,
+    pub haulers: BTreeMap<ShipId, Hauler>,
+    pub depots: BTreeMap<Point<i64>, Depot>,
+    pub transport_requests: Vec<TransportRequest>,
+    /// Spatial indices over the keys of `discovered_asteroids`, `colonies` and `depots`,
+    /// kept in sync by the methods that add or remove them.
+    pub asteroid_index: SpatialIndex,
+    pub colony_index: SpatialIndex,
+    pub depot_index: SpatialIndex,
+    next_ship_id: u64,
+    next_human_id: u64,
+    next_lineage_id: u64,
 }
 
 impl SimState {
     pub fn new(settings: settings::SimulationSettings, seed: u64) -> Self {
         Self {
             seed,
+            tick: 0,
             settings,
+            resource_types: Default::default(),
             discovered_asteroids: Default::default(),
             depleted_asteroids: Default::default(),
+            explored: Default::default(),
             colonies: Default::default(),
+            scouts: Default::default(),
+            miners: Default::default(),
+            haulers: Default::default(),
+            depots: Default::default(),
+            transport_requests: Default::default(),
+            asteroid_index: Default::default(),
+            colony_index: Default::default(),
+            depot_index: Default::default(),
+            next_ship_id: 0,
+            next_human_id: 0,
+            next_lineage_id: 0,
         }
     }
 
@@ -35,6 +95,46 @@ impl SimState {
     pub fn new_with_random_seed() -> Self {
         Self::new(settings::SimulationSettings::default(), rand::random())
     }
+
+    pub fn with_resource_types(mut self, resource_types: resource::ResourceRegistry) -> Self {
+        self.resource_types = resource_types;
+        self
+    }
+
+    pub fn with_colony(mut self, point: Point<i64>) -> Self {
+        self.found_colony(point);
+        self
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    pub fn advance_tick(&mut self) {
+        self.tick = self.tick.wrapping_add(1);
+    }
+
+    pub fn next_ship_id(&mut self) -> ShipId {
+        let id = ShipId(self.next_ship_id);
+        self.next_ship_id += 1;
+        id
+    }
+
+    pub fn next_human_id(&mut self) -> HumanId {
+        let id = HumanId(self.next_human_id);
+        self.next_human_id += 1;
+        id
+    }
+
+    pub fn next_lineage_id(&mut self) -> LineageId {
+        let id = LineageId(self.next_lineage_id);
+        self.next_lineage_id += 1;
+        id
+    }
 }
 
 // Procedural generation
@@ -42,18 +142,164 @@ impl SimState {
     fn asteroid_exists(&self, point: Point<i64>) -> bool {
         let normal =
             ProcHash::from_point_i64(self.seed, point, ProcHashDomain::AsteroidExists).normalized();
-        normal < self.settings.asteroid_density
+        let density = self.settings.asteroid_density;
+        let clusters = self.settings.clusters();
+        let belts = self.settings.belts();
+
+        // Rejects most cells before sampling the more expensive noise and structures.
+        // At most the 3x3 sites around a point contribute to each structure density.
+        let max_density = density * MAX_DENSITY_FACTOR + 9.0 * (clusters.density + belts.density);
+        if normal >= max_density {
+            return false;
+        }
+
+        let density = density * self.density_factor(point)
+            + clusters.density_at(self.seed, point)
+            + belts.density_at(self.seed, point);
+        normal < density
     }
 
-    fn asteroid_resource_type(&self, point: Point<i64>) -> resource::ResourceType {
-        let uniform =
+    /// Multiplier on the asteroid density from the density noise: 0 in voids, 1 at the
+    /// noise median and up to `MAX_DENSITY_FACTOR` in the densest belts.
+    fn density_factor(&self, point: Point<i64>) -> f64 {
+        let scale = self.settings.density_noise_scale;
+        if scale <= 0.0 {
+            return 1.0;
+        }
+
+        let octaves = self.settings.density_noise_octaves;
+        let noise = fractal_noise(
+            self.seed,
+            point,
+            scale,
+            octaves,
+            ProcHashDomain::DensityNoise,
+        );
+        let threshold = self.settings.density_void_threshold;
+        ((noise - threshold) / (0.5 - threshold)).clamp(0.0, MAX_DENSITY_FACTOR)
+    }
+
+    /// A primary resource with up to two secondary ones, all rolled from the local mix.
+    /// Without mixed compositions only the primary one.
+    fn asteroid_composition(&self, point: Point<i64>) -> Composition {
+        let weights: Vec<_> = self
+            .resource_types
+            .iter()
+            .map(|(resource, definition)| {
+                let richness = self.resource_richness(point, resource);
+                (resource, definition.rarity * richness)
+            })
+            .collect();
+        let pick = |normal: f64| {
+            let total: f64 = weights.iter().map(|(_, weight)| weight).sum();
+            let mut roll = normal * total;
+            for (resource_type, weight) in &weights {
+                if roll < *weight {
+                    return *resource_type;
+                }
+                roll -= weight;
+            }
+            resource::ResourceType::ICE
+        };
+
+        let primary = pick(
             ProcHash::from_point_i64(self.seed, point, ProcHashDomain::AsteroidResourceType)
-                .uniform_n(100);
-        match uniform {
-            0..=60 => resource::ResourceType::Ice,
-            61..=90 => resource::ResourceType::Iron,
-            _ => resource::ResourceType::Gold,
+                .normalized(),
+        );
+        if !self.settings.mixed_compositions {
+            return Composition::new([(primary, 1.0)]);
         }
+
+        let hash = ProcHash::from_point_i64(self.seed, point, ProcHashDomain::AsteroidComposition);
+        let roll = |salt: u64| {
+            ProcHash::from_id(hash.raw(), salt, 0, ProcHashDomain::AsteroidComposition).normalized()
+        };
+
+        let primary_share = MIN_PRIMARY_SHARE + (1.0 - MIN_PRIMARY_SHARE) * roll(0);
+        let secondary_share = (1.0 - primary_share) * roll(1);
+        let tertiary_share = 1.0 - primary_share - secondary_share;
+
+        Composition::new([
+            (primary, primary_share as f32),
+            (pick(roll(2)), secondary_share as f32),
+            (pick(roll(3)), tertiary_share as f32),
+        ])
+    }
+
+    /// Multiplier on a resource's share of the local mix, above 1 in regions rich in it.
+    /// Ice is spread evenly so colonies find water everywhere.
+    fn resource_richness(&self, point: Point<i64>, resource: resource::ResourceType) -> f64 {
+        let scale = self.settings.resource_noise_scale;
+        if scale <= 0.0 || resource == resource::ResourceType::ICE {
+            return 1.0;
+        }
+
+        let seed = ProcHash::from_id(
+            self.seed,
+            resource.0 as u64,
+            0,
+            ProcHashDomain::ResourceNoise,
+        );
+        let noise = fractal_noise(seed.raw(), point, scale, 2, ProcHashDomain::ResourceNoise);
+        (2.0 * noise).powf(self.settings.resource_noise_contrast)
+    }
+
+    fn scout_heading(&self, id: ShipId) -> Point<f32> {
+        let n = ProcHash::from_id(self.seed, id.0, 0, ProcHashDomain::ScoutHeading).normalized();
+        Point::from_angle((n * std::f64::consts::TAU) as f32)
+    }
+
+    fn human_genome(&self, id: HumanId) -> Genome {
+        Genome::from_fn(|gene| {
+            ProcHash::from_id(self.seed, id.0, gene as u64, ProcHashDomain::HumanGenome)
+                .normalized() as f32
+        })
+    }
+
+    /// Founders start at a random age up to half the base lifespan, so they don't all die at once.
+    fn founder_age(&self, id: HumanId) -> u64 {
+        let n = ProcHash::from_id(self.seed, id.0, 0, ProcHashDomain::HumanAge).normalized();
+        (n * self.settings.human_base_lifespan as f64 * 0.5) as u64
+    }
+
+    fn parent_index(&self, child: HumanId, parent: u64, population: usize) -> usize {
+        ProcHash::from_id(self.seed, child.0, parent, ProcHashDomain::ParentSelection)
+            .uniform_n(population as u64) as usize
+    }
+
+    fn child_genome(&self, child: HumanId, first: &Genome, second: &Genome) -> Genome {
+        let mutation_rate = self.settings.mutation_rate;
+        let mutation_strength = self.settings.mutation_strength;
+
+        Genome::crossover(
+            first,
+            second,
+            |gene| {
+                ProcHash::from_id(
+                    self.seed,
+                    child.0,
+                    gene as u64,
+                    ProcHashDomain::GeneCrossover,
+                )
+                .uniform_n(2)
+                    == 0
+            },
+            |gene| {
+                let hash = ProcHash::from_id(
+                    self.seed,
+                    child.0,
+                    gene as u64,
+                    ProcHashDomain::GeneMutation,
+                );
+                let roll = hash.normalized();
+                if roll < mutation_rate {
+                    // Reuse the roll below the rate as the mutation offset in [-strength, strength].
+                    ((roll / mutation_rate) as f32 * 2.0 - 1.0) * mutation_strength
+                } else {
+                    0.0
+                }
+            },
+        )
     }
 
     fn asteroid_initial_amount(&self, point: Point<i64>) -> f32 {
@@ -63,6 +309,226 @@ impl SimState {
     }
 }
 
+// World updates
+impl SimState {
+    pub fn spawn_founder(&mut self) -> Human {
+        let id = self.next_human_id();
+        Human {
+            id,
+            lineage: self.next_lineage_id(),
+            generation: 0,
+            genome: self.human_genome(id),
+            age: self.founder_age(id),
+        }
+    }
+
+    /// Breeds a child from two members of the colony's population, paid for with ice above the reserve.
+    pub fn reproduce(&mut self, point: Point<i64>) -> Option<HumanId> {
+        let ice_cost = self.settings.reproduction_ice_cost;
+        let ice_reserve = self.settings.reproduction_ice_reserve;
+        let colony = self.colonies.get(&point)?;
+        let population = colony.population.len();
+        let ice = colony.resources.total(resource::ResourceType::ICE);
+        if population < 2 || ice < ice_cost + ice_reserve {
+            return None;
+        }
+
+        let id = HumanId(self.next_human_id);
+        let first_index = self.parent_index(id, 0, population);
+        let offset = self.parent_index(id, 1, population - 1);
+        let second_index = (first_index + 1 + offset) % population;
+
+        let first = &colony.population[first_index];
+        let second = &colony.population[second_index];
+        let child = Human {
+            id,
+            lineage: first.lineage,
+            generation: first.generation.max(second.generation) + 1,
+            genome: self.child_genome(id, &first.genome, &second.genome),
+            age: 0,
+        };
+
+        self.next_human_id();
+        let colony = self.colonies.get_mut(&point)?;
+        colony
+            .resources
+            .remove(resource::ResourceType::ICE, ice_cost);
+        colony.population.push(child);
+        Some(id)
+    }
+
+    /// Founds a colony with a fresh set of founders, does nothing if one already exists there.
+    /// Like a settlement it starts with the founding ice, so it doesn't starve before the first delivery.
+    pub fn found_colony(&mut self, point: Point<i64>) -> bool {
+        if self.colonies.contains_key(&point) {
+            return false;
+        }
+
+        let founders = (0..self.settings.colony_founders)
+            .map(|_| self.spawn_founder())
+            .collect();
+        let hulls = self.settings.colony_initial_hulls;
+        let mut colony = Colony::new(founders, hulls);
+        colony.resources.add(
+            resource::ResourceType::ICE,
+            self.settings.colony_founding_ice,
+        );
+        self.colonies.insert(point, colony);
+        self.colony_index.insert(point);
+        true
+    }
+
+    /// Sends settlers and resources from an existing colony to found a new one at `site`.
+    pub fn settle_colony(&mut self, from: Point<i64>, site: Point<i64>) -> bool {
+        let iron_cost = self.settings.colony_founding_iron;
+        let ice_cost = self.settings.colony_founding_ice;
+        let settlers = self.settings.colony_settlers;
+        let hulls = self.settings.colony_settler_hulls;
+
+        if !self.can_expand(from) || self.colonies.contains_key(&site) {
+            return false;
+        }
+        let Some(colony) = self.colonies.get_mut(&from) else {
+            return false;
+        };
+
+        colony
+            .resources
+            .remove(resource::ResourceType::IRON, iron_cost);
+        colony
+            .resources
+            .remove(resource::ResourceType::ICE, ice_cost);
+        colony.remove_product(Product::Hull, hulls);
+        let settlers = (0..settlers)
+            .filter_map(|_| colony.take_pilot(|genome| genome.get(Gene::RiskTolerance)))
+            .collect();
+
+        let mut settlement = Colony::new(settlers, hulls);
+        settlement
+            .resources
+            .add(resource::ResourceType::IRON, iron_cost);
+        settlement
+            .resources
+            .add(resource::ResourceType::ICE, ice_cost);
+        self.colonies.insert(site, settlement);
+        self.colony_index.insert(site);
+        true
+    }
+
+    /// Takes a hull and the best suited pilot from the colony to launch a ship.
+    fn crew_ship(&mut self, home: Point<i64>, score: impl Fn(&Genome) -> f32) -> Option<Human> {
+        let colony = self.colonies.get_mut(&home)?;
+        if colony.population.is_empty() || !colony.remove_product(Product::Hull, 1.0) {
+            return None;
+        }
+        colony.take_pilot(score)
+    }
+
+    /// Returns the hull and pilot of a docking ship, they are lost if the colony no longer exists.
+    pub fn dock_ship(&mut self, home: Point<i64>, pilot: Human) {
+        if let Some(colony) = self.colonies.get_mut(&home) {
+            colony.add_product(Product::Hull, 1.0);
+            colony.population.push(pilot);
+        }
+    }
+
+    pub fn launch_scout(&mut self, home: Point<i64>) -> Option<ShipId> {
+        let pilot = self.crew_ship(home, |genome| {
+            genome.get(Gene::Perception) + genome.get(Gene::RiskTolerance)
+        })?;
+        let id = self.next_ship_id();
+        let scout = Scout::new(home, pilot, self.scout_heading(id));
+        self.scouts.insert(id, scout);
+        Some(id)
+    }
+
+    pub fn launch_miner(&mut self, home: Point<i64>, target: Point<i64>) -> Option<ShipId> {
+        let pilot = self.crew_ship(home, |genome| genome.get(Gene::MiningEfficiency))?;
+        let id = self.next_ship_id();
+        self.miners.insert(id, Miner::new(home, pilot, target));
+        Some(id)
+    }
+
+    /// Mines up to `amount` from the asteroid, returns the mined resources split by its composition.
+    pub fn mine_asteroid(&mut self, point: Point<i64>, amount: f32) -> Option<ResourceBag> {
+        let composition = self.composition_at(point)?;
+        let remaining = self.resource_amount_at(point)?;
+        let initial_amount = self.asteroid_initial_amount(point);
+        let mined_amount = self.discovered_asteroids.get_mut(&point)?;
+
+        if amount >= remaining {
+            *mined_amount = initial_amount;
+            Some(composition.split(remaining))
+        } else {
+            *mined_amount += amount;
+            Some(composition.split(amount))
+        }
+    }
+
+    pub fn discover_asteroid(&mut self, point: Point<i64>) {
+        self.discovered_asteroids.insert(point, 0.0);
+        self.asteroid_index.insert(point);
+    }
+
+    pub fn deplete_asteroid(&mut self, point: Point<i64>) {
+        self.discovered_asteroids.remove(&point);
+        self.asteroid_index.remove(point);
+        self.depleted_asteroids.insert(point);
+    }
+
+    pub fn establish_depot(&mut self, point: Point<i64>) {
+        self.depots.insert(point, Depot::default());
+        self.depot_index.insert(point);
+    }
+
+    /// Rebuilds the spatial indices from the entity maps, e.g. after loading.
+    pub fn rebuild_indices(&mut self) {
+        self.asteroid_index = self.discovered_asteroids.keys().copied().collect();
+        self.colony_index = self.colonies.keys().copied().collect();
+        self.depot_index = self.depots.keys().copied().collect();
+    }
+
+    pub fn launch_hauler(
+        &mut self,
+        home: Point<i64>,
+        source: Stockpile,
+        request: TransportRequest,
+    ) -> Option<ShipId> {
+        let pilot = self.crew_ship(home, |genome| genome.get(Gene::Speed))?;
+        let id = self.next_ship_id();
+        self.haulers
+            .insert(id, Hauler::new(home, pilot, source, request));
+        self.transport_requests.retain(|open| *open != request);
+        Some(id)
+    }
+
+    pub fn post_transport_requests(&mut self) {
+        let target = self.settings.colony_stock_target;
+        let min_amount = self.settings.min_transport_amount;
+
+        let mut requests: Vec<_> = self
+            .colonies
+            .iter()
+            .flat_map(|(point, colony)| {
+                colony.transport_requests(*point, target, self.resource_types.types(), |resource| {
+                    self.incoming_amount(*point, resource)
+                })
+            })
+            .filter(|request| request.amount >= min_amount)
+            .collect();
+        requests.sort_by_key(|request| Reverse(request.priority));
+
+        self.transport_requests = requests;
+    }
+
+    pub fn stockpile_mut(&mut self, stockpile: Stockpile) -> Option<&mut ResourceBag> {
+        match stockpile {
+            Stockpile::Colony(point) => self.colonies.get_mut(&point).map(|c| &mut c.resources),
+            Stockpile::Depot(point) => self.depots.get_mut(&point).map(|d| &mut d.resources),
+        }
+    }
+}
+
 // World queries
 impl SimState {
     pub fn has_asteroid_resources(&self, point: Point<i64>) -> bool {
@@ -81,11 +547,11 @@ impl SimState {
         }
     }
 
-    pub fn resource_type_at(&self, point: Point<i64>) -> Option<resource::ResourceType> {
+    pub fn composition_at(&self, point: Point<i64>) -> Option<Composition> {
         if !self.has_asteroid_resources(point) {
             return None;
         }
-        Some(self.asteroid_resource_type(point))
+        Some(self.asteroid_composition(point))
     }
 
     pub fn resource_amount_at(&self, point: Point<i64>) -> Option<f32> {
@@ -93,11 +559,15 @@ impl SimState {
         Some((self.asteroid_initial_amount(point) - mined_amount).max(0.0))
     }
 
-    pub fn asteroid_scale_at(&self, point: Point<i64>) -> Option<f32> {
+    /// Denser resources pack the same amount into a smaller asteroid.
+    /// Takes the asteroid's composition from [`Self::composition_at`] to avoid generating it twice.
+    pub fn asteroid_scale_at(&self, point: Point<i64>, composition: &Composition) -> Option<f32> {
         let resource_amount = self.resource_amount_at(point)?;
+        let density = composition.density(&self.resource_types);
         Some(
             resource_amount / self.settings.max_asteroid_resource_amount
-                * self.settings.max_asteroid_scale,
+                * self.settings.max_asteroid_scale
+                / density.cbrt(),
         )
     }
 
@@ -108,4 +578,192 @@ impl SimState {
     pub fn colony_at(&self, point: Point<i64>) -> Option<&Colony> {
         self.colonies.get(&point)
     }
+
+    pub fn miners_from(&self, home: Point<i64>) -> usize {
+        self.miners
+            .values()
+            .filter(|miner| miner.home == home)
+            .count()
+    }
+
+    /// Finds the nearest discovered asteroid within `range` of `origin` that no miner is targeting yet.
+    pub fn nearest_unclaimed_asteroid(&self, origin: Point<f32>, range: f32) -> Option<Point<i64>> {
+        let claimed: BTreeSet<_> = self.miners.values().filter_map(Miner::target).collect();
+
+        self.asteroid_index
+            .nearest(origin, range, |point| !claimed.contains(&point))
+    }
+
+    pub fn haulers_from(&self, home: Point<i64>) -> usize {
+        self.haulers
+            .values()
+            .filter(|hauler| hauler.home == home)
+            .count()
+    }
+
+    /// The amount of a resource currently being hauled towards a colony.
+    pub fn incoming_amount(&self, colony: Point<i64>, resource: resource::ResourceType) -> f32 {
+        self.haulers
+            .values()
+            .map(|hauler| match hauler.task {
+                HaulerTask::Pickup(_, request)
+                    if request.colony == colony && request.resource == resource =>
+                {
+                    request.amount.min(self.settings.hauler_capacity)
+                }
+                HaulerTask::Deliver(request)
+                    if request.colony == colony && request.resource == resource =>
+                {
+                    hauler.cargo.total(resource)
+                }
+                _ => 0.0,
+            })
+            .sum()
+    }
+
+    /// The amount of a resource haulers may take from a stockpile.
+    pub fn available_at(&self, stockpile: Stockpile, resource: resource::ResourceType) -> f32 {
+        match stockpile {
+            Stockpile::Colony(point) => self.colonies.get(&point).map_or(0.0, |colony| {
+                colony.surplus(resource, self.settings.colony_stock_target)
+            }),
+            Stockpile::Depot(point) => self
+                .depots
+                .get(&point)
+                .map_or(0.0, |depot| depot.resources.total(resource)),
+        }
+    }
+
+    /// Picks the open request with the highest priority and the shortest haul for a hauler from `home`.
+    pub fn best_transport(&self, home: Point<i64>) -> Option<(Stockpile, TransportRequest)> {
+        let min_amount = self.settings.min_transport_amount;
+        let stockpiles: Vec<_> = self
+            .colonies
+            .keys()
+            .map(|point| Stockpile::Colony(*point))
+            .chain(self.depots.keys().map(|point| Stockpile::Depot(*point)))
+            .collect();
+
+        self.transport_requests
+            .iter()
+            .flat_map(|request| {
+                stockpiles
+                    .iter()
+                    .filter(|source| source.point() != request.colony)
+                    .filter(|source| self.available_at(**source, request.resource) >= min_amount)
+                    .map(move |source| {
+                        let source_point = source.point().to_f32();
+                        let distance = home.to_f32().distance(source_point)
+                            + source_point.distance(request.colony.to_f32());
+                        (*source, *request, distance)
+                    })
+            })
+            .min_by(|(_, a, a_distance), (_, b, b_distance)| {
+                b.priority
+                    .cmp(&a.priority)
+                    .then(a_distance.total_cmp(b_distance))
+            })
+            .map(|(source, request, _)| (source, request))
+    }
+
+    pub fn nearest_depot(&self, origin: Point<f32>, range: f32) -> Option<Point<i64>> {
+        self.depot_index.nearest(origin, range, |_| true)
+    }
+
+    /// All living humans, including pilots currently out on ships.
+    pub fn population(&self) -> usize {
+        let colonists: usize = self.colonies.values().map(|c| c.population.len()).sum();
+        colonists + self.scouts.len() + self.miners.len() + self.haulers.len()
+    }
+
+    /// Everyone belonging to a colony, including its pilots currently out on ships.
+    pub fn colony_members(&self, home: Point<i64>) -> impl Iterator<Item = &Human> {
+        let colonists = self
+            .colonies
+            .get(&home)
+            .into_iter()
+            .flat_map(|colony| colony.population.iter());
+        let scouts = self.scouts.values().filter(move |s| s.home == home);
+        let miners = self.miners.values().filter(move |m| m.home == home);
+        let haulers = self.haulers.values().filter(move |h| h.home == home);
+
+        colonists
+            .chain(scouts.map(|scout| &scout.pilot))
+            .chain(miners.map(|miner| &miner.pilot))
+            .chain(haulers.map(|hauler| &hauler.pilot))
+    }
+
+    pub fn can_expand(&self, point: Point<i64>) -> bool {
+        let Some(colony) = self.colonies.get(&point) else {
+            return false;
+        };
+
+        colony.product(Product::Hull) >= self.settings.colony_settler_hulls
+            && colony.resources.total(resource::ResourceType::IRON)
+                >= self.settings.colony_founding_iron
+            && colony.resources.total(resource::ResourceType::ICE)
+                >= self.settings.colony_founding_ice
+            && colony.population.len() > self.settings.colony_settlers + 1
+    }
+
+    pub fn is_valid_colony_site(&self, site: Point<i64>) -> bool {
+        let min_spacing = self.settings.colony_min_spacing;
+        self.colony_index
+            .query_radius(site.to_f32(), min_spacing)
+            .all(|colony| colony.to_f32().distance(site.to_f32()) >= min_spacing)
+    }
+
+    /// Finds the richest cluster of discovered asteroids within founding range of a colony and
+    /// returns its resource-weighted center.
+    pub fn colony_site(&self, origin: Point<i64>) -> Option<Point<i64>> {
+        let range = self.settings.colony_founding_range;
+        let site_radius = self.settings.colony_site_radius;
+
+        let candidates: BTreeMap<_, _> = self
+            .asteroid_index
+            .query_radius(origin.to_f32(), range)
+            .filter_map(|point| Some((point, self.resource_amount_at(point)?)))
+            .collect();
+
+        candidates
+            .keys()
+            .filter_map(|center| {
+                let mut total = 0.0;
+                let mut weighted = Point::new(0.0, 0.0);
+                let cluster = self
+                    .asteroid_index
+                    .query_radius(center.to_f32(), site_radius);
+                for point in cluster {
+                    let Some(amount) = candidates.get(&point) else {
+                        continue;
+                    };
+                    total += amount;
+                    weighted = weighted + point.to_f32() * *amount;
+                }
+                if total <= 0.0 {
+                    return None;
+                }
+
+                let site = (weighted / total).round().to_i64();
+                self.is_valid_colony_site(site).then_some((site, total))
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(site, _)| site)
+    }
+
+    /// The mining rate multiplier a miner gets from its home colony's upgrades.
+    pub fn mining_bonus(&self, miner: ShipId) -> f32 {
+        let bonus_per_upgrade = self.settings.upgrade_mining_bonus;
+        self.miners
+            .get(&miner)
+            .and_then(|miner| self.colonies.get(&miner.home))
+            .map_or(1.0, |colony| colony.mining_bonus(bonus_per_upgrade))
+    }
+
+    pub fn scouts_from(&self, home: Point<i64>) -> usize {
+        self.scouts
+            .values()
+            .filter(|scout| scout.home == home)
+            .count()
+    }
 }
@@ -1,6 +1,164 @@
+use crate::math::point::Point;
+use crate::simulation::state::human::{Genome, Human};
+use crate::simulation::state::logistics::TransportRequest;
+use crate::simulation::state::production::{Product, ProductionRates, Recipe};
+use crate::simulation::state::resource::ResourceType;
 use crate::simulation::state::resource_bag::ResourceBag;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Default, Clone)]
 pub struct Colony {
-    resources: ResourceBag,
+    pub resources: ResourceBag,
+    pub population: Vec<Human>,
+    pub products: BTreeMap<Product, f32>,
+    pub production: ProductionRates,
+}
+
+impl Colony {
+    pub fn new(population: Vec<Human>, hulls: f32) -> Self {
+        Self {
+            resources: ResourceBag::default(),
+            population,
+            products: BTreeMap::from([(Product::Hull, hulls)]),
+            production: ProductionRates::default(),
+        }
+    }
+
+    pub fn product(&self, product: Product) -> f32 {
+        self.products.get(&product).copied().unwrap_or(0.0)
+    }
+
+    pub fn add_product(&mut self, product: Product, amount: f32) {
+        *self.products.entry(product).or_default() += amount;
+    }
+
+    pub fn remove_product(&mut self, product: Product, amount: f32) -> bool {
+        match self.products.get_mut(&product) {
+            Some(stock) if *stock >= amount => {
+                *stock -= amount;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn mining_bonus(&self, bonus_per_upgrade: f32) -> f32 {
+        1.0 + self.product(Product::Upgrade).floor() * bonus_per_upgrade
+    }
+
+    /// Feeds the population and runs every recipe the colony has inputs for.
+    /// When the ice can't cover the upkeep, what there is gets eaten and the colony starves.
+    pub fn produce(&mut self, recipes: &[Recipe], ice_upkeep_per_human: f32) {
+        self.production = ProductionRates::default();
+
+        let upkeep = self.population.len() as f32 * ice_upkeep_per_human;
+        let eaten = upkeep.min(self.resources.total(ResourceType::ICE));
+        if eaten > 0.0 && self.resources.remove(ResourceType::ICE, eaten) {
+            self.production.consumed.insert(ResourceType::ICE, eaten);
+        }
+        self.production.ice_shortage = upkeep - eaten;
+        self.production.starving = self.production.ice_shortage > 0.0;
+
+        for recipe in recipes {
+            if self.product(recipe.output) >= recipe.max_stock {
+                continue;
+            }
+            let has_inputs = recipe
+                .inputs
+                .iter()
+                .all(|(resource, amount)| self.resources.total(*resource) >= *amount);
+            if !has_inputs {
+                continue;
+            }
+
+            for (resource, amount) in &recipe.inputs {
+                self.resources.remove(*resource, *amount);
+                *self.production.consumed.entry(*resource).or_default() += amount;
+            }
+            self.add_product(recipe.output, recipe.output_amount);
+            *self.production.produced.entry(recipe.output).or_default() += recipe.output_amount;
+        }
+    }
+
+    /// Takes the best suited human according to `score` out of the population to pilot a ship.
+    pub fn take_pilot(&mut self, score: impl Fn(&Genome) -> f32) -> Option<Human> {
+        let index = self
+            .population
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| score(&a.genome).total_cmp(&score(&b.genome)))
+            .map(|(index, _)| index)?;
+        Some(self.population.swap_remove(index))
+    }
+
+    /// Requests every resource whose stock (including `incoming` shipments) is below `target`.
+    pub fn transport_requests<'a>(
+        &'a self,
+        point: Point<i64>,
+        target: f32,
+        resources: impl Iterator<Item = ResourceType> + 'a,
+        incoming: impl Fn(ResourceType) -> f32 + 'a,
+    ) -> impl Iterator<Item = TransportRequest> + 'a {
+        resources.filter_map(move |resource| {
+            let stock = self.resources.total(resource) + incoming(resource);
+            let deficit = target - stock;
+            if deficit <= 0.0 {
+                return None;
+            }
+
+            Some(TransportRequest {
+                colony: point,
+                resource,
+                amount: deficit,
+                priority: (deficit / target * u8::MAX as f32) as u8,
+            })
+        })
+    }
+
+    /// The amount of a resource the colony can spare without dropping below `target`.
+    pub fn surplus(&self, resource: ResourceType, target: f32) -> f32 {
+        (self.resources.total(resource) - target).max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::state::human::{HumanId, LineageId};
+
+    fn colony_with(humans: u64, ice: f32) -> Colony {
+        let population = (0..humans)
+            .map(|id| Human {
+                id: HumanId(id),
+                lineage: LineageId(id),
+                generation: 0,
+                genome: Genome::default(),
+                age: 0,
+            })
+            .collect();
+        let mut colony = Colony::new(population, 0.0);
+        colony.resources.add(ResourceType::ICE, ice);
+        colony
+    }
+
+    #[test]
+    fn covered_upkeep_is_consumed() {
+        let mut colony = colony_with(4, 10.0);
+        colony.produce(&[], 0.5);
+
+        assert!(!colony.production.starving);
+        assert_eq!(colony.production.consumed[&ResourceType::ICE], 2.0);
+        assert_eq!(colony.resources.total(ResourceType::ICE), 8.0);
+    }
+
+    #[test]
+    fn partial_upkeep_eats_what_there_is_and_starves() {
+        let mut colony = colony_with(4, 1.5);
+        colony.produce(&[], 0.5);
+
+        assert!(colony.production.starving);
+        assert_eq!(colony.production.consumed[&ResourceType::ICE], 1.5);
+        assert_eq!(colony.production.ice_shortage, 0.5);
+        assert_eq!(colony.resources.total(ResourceType::ICE), 0.0);
+    }
 }
@@ -0,0 +1,73 @@
+use crate::math::rgba::RGBA;
+use crate::simulation::state::resource::{ResourceRegistry, ResourceType};
+use crate::simulation::state::resource_bag::ResourceBag;
+
+/// The resources an asteroid is made of, with shares summing to 1, largest first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Composition {
+    shares: Vec<(ResourceType, f32)>,
+}
+
+impl Composition {
+    /// Merges duplicate resources and normalizes the shares.
+    pub fn new(shares: impl IntoIterator<Item = (ResourceType, f32)>) -> Self {
+        let mut merged: Vec<(ResourceType, f32)> = Vec::new();
+        for (resource, share) in shares {
+            match merged.iter_mut().find(|(other, _)| *other == resource) {
+                Some((_, existing)) => *existing += share,
+                None => merged.push((resource, share)),
+            }
+        }
+
+        let total: f32 = merged.iter().map(|(_, share)| share).sum();
+        if total > 0.0 {
+            for (_, share) in &mut merged {
+                *share /= total;
+            }
+        }
+        merged.retain(|(_, share)| *share > 0.0);
+        merged.sort_by(|(a, a_share), (b, b_share)| b_share.total_cmp(a_share).then(a.cmp(b)));
+
+        Self { shares: merged }
+    }
+
+    /// Splits a mined amount into the resources it consists of.
+    pub fn split(&self, amount: f32) -> ResourceBag {
+        let mut bag = ResourceBag::default();
+        for (resource, share) in &self.shares {
+            bag.add(*resource, amount * share);
+        }
+        bag
+    }
+
+    /// Average density weighted by share.
+    pub fn density(&self, resource_types: &ResourceRegistry) -> f32 {
+        self.shares
+            .iter()
+            .map(|(resource, share)| {
+                let density = resource_types.get(*resource).map_or(1.0, |d| d.density);
+                density * share
+            })
+            .sum()
+    }
+
+    /// The resource colors mixed by share.
+    pub fn blended_color(&self, resource_types: &ResourceRegistry) -> RGBA {
+        let mut channels = [0.0f32; 3];
+        for (resource, share) in &self.shares {
+            let color = resource_types.color(*resource);
+            channels[0] += color.r() as f32 * share;
+            channels[1] += color.g() as f32 * share;
+            channels[2] += color.b() as f32 * share;
+        }
+        RGBA::rgb(channels[0] as u8, channels[1] as u8, channels[2] as u8)
+    }
+
+    /// Each resource color with its share, for dithering.
+    pub fn palette(&self, resource_types: &ResourceRegistry) -> Vec<(RGBA, f32)> {
+        self.shares
+            .iter()
+            .map(|(resource, share)| (resource_types.color(*resource), *share))
+            .collect()
+    }
+}
@@ -0,0 +1,6 @@
+use crate::simulation::state::resource_bag::ResourceBag;
+
+#[derive(Debug, Default, Clone)]
+pub struct Depot {
+    pub resources: ResourceBag,
+}
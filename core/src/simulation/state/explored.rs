@@ -0,0 +1,69 @@
+use crate::math::point::Point;
+use crate::math::rect::Rect;
+use std::collections::BTreeMap;
+
+pub const CHUNK_SIZE: i64 = 64;
+
+/// One bit per cell of a chunk, each row of cells packed into a `u64`.
+pub type ChunkBits = [u64; CHUNK_SIZE as usize];
+
+/// Cells that have been scanned, stored as bitmaps of fixed-size chunks.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ExploredMap {
+    pub chunks: BTreeMap<Point<i64>, Box<ChunkBits>>,
+}
+
+impl ExploredMap {
+    pub fn is_explored(&self, point: Point<i64>) -> bool {
+        let (chunk, x, y) = split(point);
+        self.chunks
+            .get(&chunk)
+            .is_some_and(|bits| bits[y] & (1 << x) != 0)
+    }
+
+    /// Marks the cell as explored, returns whether it wasn't before.
+    pub fn mark(&mut self, point: Point<i64>) -> bool {
+        let (chunk, x, y) = split(point);
+        let row = &mut self
+            .chunks
+            .entry(chunk)
+            .or_insert_with(|| Box::new([0; CHUNK_SIZE as usize]))[y];
+
+        let newly_explored = *row & (1 << x) == 0;
+        *row |= 1 << x;
+        newly_explored
+    }
+
+    pub fn explored_count(&self) -> u64 {
+        self.chunks
+            .values()
+            .flat_map(|bits| bits.iter())
+            .map(|row| row.count_ones() as u64)
+            .sum()
+    }
+
+    /// Chunk coordinates and bits of every chunk overlapping the rect, including its max edges.
+    pub fn chunks_in_rect(
+        &self,
+        rect: Rect<i64>,
+    ) -> impl Iterator<Item = (Point<i64>, &ChunkBits)> + '_ {
+        let (min, ..) = split(rect.min);
+        let (max, ..) = split(rect.max);
+
+        self.chunks
+            .range(Point::new(min.x, i64::MIN)..=Point::new(max.x, i64::MAX))
+            .filter(move |(chunk, _)| (min.y..=max.y).contains(&chunk.y))
+            .map(|(chunk, bits)| (*chunk, bits.as_ref()))
+    }
+}
+
+/// The chunk containing the point and the point's column and row within it.
+fn split(point: Point<i64>) -> (Point<i64>, usize, usize) {
+    let chunk = Point::new(
+        point.x.div_euclid(CHUNK_SIZE),
+        point.y.div_euclid(CHUNK_SIZE),
+    );
+    let x = point.x.rem_euclid(CHUNK_SIZE) as usize;
+    let y = point.y.rem_euclid(CHUNK_SIZE) as usize;
+    (chunk, x, y)
+}
@@ -0,0 +1,37 @@
+use crate::math::point::Point;
+use crate::simulation::state::human::Human;
+use crate::simulation::state::logistics::{Stockpile, TransportRequest};
+use crate::simulation::state::resource_bag::ResourceBag;
+
+#[derive(Debug, Clone)]
+pub struct Hauler {
+    pub home: Point<i64>,
+    pub pilot: Human,
+    pub position: Point<f32>,
+    pub cargo: ResourceBag,
+    pub task: HaulerTask,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HaulerTask {
+    Pickup(Stockpile, TransportRequest),
+    Deliver(TransportRequest),
+    Returning,
+}
+
+impl Hauler {
+    pub fn new(
+        home: Point<i64>,
+        pilot: Human,
+        source: Stockpile,
+        request: TransportRequest,
+    ) -> Self {
+        Self {
+            home,
+            pilot,
+            position: home.to_f32(),
+            cargo: ResourceBag::default(),
+            task: HaulerTask::Pickup(source, request),
+        }
+    }
+}
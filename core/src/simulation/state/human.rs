@@ -0,0 +1,111 @@
+use std::fmt::Display;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct HumanId(pub u64);
+
+/// Identifies the founder a human descends from.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct LineageId(pub u64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Gene {
+    Speed,
+    Perception,
+    MiningEfficiency,
+    RiskTolerance,
+    Lifespan,
+}
+
+impl Gene {
+    pub const ALL: [Gene; 5] = [
+        Gene::Speed,
+        Gene::Perception,
+        Gene::MiningEfficiency,
+        Gene::RiskTolerance,
+        Gene::Lifespan,
+    ];
+}
+
+impl Display for Gene {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Gene::Speed => write!(f, "Speed"),
+            Gene::Perception => write!(f, "Perception"),
+            Gene::MiningEfficiency => write!(f, "Mining Efficiency"),
+            Gene::RiskTolerance => write!(f, "Risk Tolerance"),
+            Gene::Lifespan => write!(f, "Lifespan"),
+        }
+    }
+}
+
+/// Normalized gene values in [0, 1], expressed as trait multipliers around 1.0.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Genome([f32; Gene::ALL.len()]);
+
+impl Genome {
+    pub fn from_fn(mut f: impl FnMut(Gene) -> f32) -> Self {
+        Self(Gene::ALL.map(|gene| f(gene).clamp(0.0, 1.0)))
+    }
+
+    pub fn get(&self, gene: Gene) -> f32 {
+        self.0[gene as usize]
+    }
+
+    /// Takes each gene from either parent as decided by `pick_first`, then applies `mutate`.
+    pub fn crossover(
+        first: &Genome,
+        second: &Genome,
+        mut pick_first: impl FnMut(Gene) -> bool,
+        mut mutate: impl FnMut(Gene) -> f32,
+    ) -> Self {
+        Self::from_fn(|gene| {
+            let inherited = if pick_first(gene) {
+                first.get(gene)
+            } else {
+                second.get(gene)
+            };
+            inherited + mutate(gene)
+        })
+    }
+
+    /// Multiplier for ship speed in [0.75, 1.25].
+    pub fn speed(&self) -> f32 {
+        0.75 + 0.5 * self.get(Gene::Speed)
+    }
+
+    /// Multiplier for scout sensor radius in [0.5, 1.5].
+    pub fn perception(&self) -> f32 {
+        0.5 + self.get(Gene::Perception)
+    }
+
+    /// Multiplier for mining rate in [0.5, 1.5].
+    pub fn mining_efficiency(&self) -> f32 {
+        0.5 + self.get(Gene::MiningEfficiency)
+    }
+
+    /// Multiplier for how far from home a pilot is willing to fly in [0.5, 1.5].
+    pub fn risk_tolerance(&self) -> f32 {
+        0.5 + self.get(Gene::RiskTolerance)
+    }
+
+    pub fn lifespan(&self, base_lifespan: u64) -> u64 {
+        (base_lifespan as f32 * (0.5 + self.get(Gene::Lifespan))) as u64
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Human {
+    pub id: HumanId,
+    pub lineage: LineageId,
+    pub generation: u32,
+    pub genome: Genome,
+    pub age: u64,
+}
+
+impl Human {
+    pub fn is_alive(&self, base_lifespan: u64) -> bool {
+        self.age < self.genome.lifespan(base_lifespan)
+    }
+}
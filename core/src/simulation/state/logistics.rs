@@ -0,0 +1,26 @@
+use crate::math::point::Point;
+use crate::simulation::state::resource::ResourceType;
+
+/// A colony asking for resources to be brought to it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransportRequest {
+    pub colony: Point<i64>,
+    pub resource: ResourceType,
+    pub amount: f32,
+    pub priority: u8,
+}
+
+/// A place haulers can pick resources up from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stockpile {
+    Colony(Point<i64>),
+    Depot(Point<i64>),
+}
+
+impl Stockpile {
+    pub fn point(&self) -> Point<i64> {
+        match self {
+            Stockpile::Colony(point) | Stockpile::Depot(point) => *point,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use crate::math::point::Point;
+use crate::simulation::state::human::Human;
+use crate::simulation::state::resource_bag::ResourceBag;
+
+#[derive(Debug, Clone)]
+pub struct Miner {
+    pub home: Point<i64>,
+    pub pilot: Human,
+    pub position: Point<f32>,
+    pub cargo: ResourceBag,
+    pub task: MinerTask,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MinerTask {
+    Travelling(Point<i64>),
+    Mining(Point<i64>),
+    Delivering(Point<i64>),
+    Returning,
+}
+
+impl Miner {
+    pub fn new(home: Point<i64>, pilot: Human, target: Point<i64>) -> Self {
+        Self {
+            home,
+            pilot,
+            position: home.to_f32(),
+            cargo: ResourceBag::default(),
+            task: MinerTask::Travelling(target),
+        }
+    }
+
+    pub fn target(&self) -> Option<Point<i64>> {
+        match self.task {
+            MinerTask::Travelling(target) | MinerTask::Mining(target) => Some(target),
+            MinerTask::Delivering(_) | MinerTask::Returning => None,
+        }
+    }
+}
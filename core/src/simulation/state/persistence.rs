@@ -0,0 +1,755 @@
+use crate::math::rgba::RGBA;
+use crate::simulation::layer::Layer;
+use crate::simulation::replay::RecordedCommand;
+use crate::simulation::state::colony::Colony;
+use crate::simulation::state::depot::Depot;
+use crate::simulation::state::explored::ExploredMap;
+use crate::simulation::state::hauler::{Hauler, HaulerTask};
+use crate::simulation::state::human::{Gene, Genome, Human, HumanId, LineageId};
+use crate::simulation::state::logistics::{Stockpile, TransportRequest};
+use crate::simulation::state::miner::{Miner, MinerTask};
+use crate::simulation::state::production::{Product, ProductionRates, Recipe};
+use crate::simulation::state::resource::{
+    ResourceDefinition, ResourceRegistry, ResourceRegistryError, ResourceType,
+};
+use crate::simulation::state::resource_bag::ResourceBag;
+use crate::simulation::state::scout::Scout;
+use crate::simulation::state::settings::{SettingsError, SimulationSettings};
+use crate::simulation::state::ship::ShipId;
+use crate::simulation::state::SimState;
+use crate::simulation::sync::command::SimCommand;
+use codec::{Decode, Encode, Reader, Writer};
+use rapidhash::fast::RapidHasher;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::hash::Hasher;
+use std::path::Path;
+
+pub mod codec;
+pub mod migration;
+
+const MAGIC: [u8; 4] = *b"PSSV";
+pub const FORMAT_VERSION: u32 = 7;
+
+const SECTION_WORLD: &str = "world";
+const SECTION_SETTINGS: &str = "settings";
+const SECTION_ASTEROIDS: &str = "asteroids";
+const SECTION_COLONIES: &str = "colonies";
+const SECTION_SHIPS: &str = "ships";
+const SECTION_LOGISTICS: &str = "logistics";
+const SECTION_RESOURCES: &str = "resources";
+const SECTION_EXPLORED: &str = "explored";
+
+#[derive(Debug)]
+pub enum PersistenceError {
+    Io(std::io::Error),
+    InvalidMagic,
+    UnsupportedVersion(u32),
+    NewerVersion { found: u32, supported: u32 },
+    MissingSection(String),
+    UnexpectedEof,
+    TrailingBytes,
+    InvalidLength,
+    InvalidUtf8,
+    InvalidTag { kind: &'static str, tag: u8 },
+    InvalidResources(ResourceRegistryError),
+    InvalidSettings(SettingsError),
+}
+
+impl Display for PersistenceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PersistenceError::Io(error) => write!(f, "I/O error: {error}"),
+            PersistenceError::InvalidMagic => write!(f, "Not a simulation save file"),
+            PersistenceError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported save format version {version}")
+            }
+            PersistenceError::NewerVersion { found, supported } => write!(
+                f,
+                "Save format version {found} is newer than the supported version {supported}"
+            ),
+            PersistenceError::MissingSection(name) => write!(f, "Missing save section '{name}'"),
+            PersistenceError::UnexpectedEof => write!(f, "Unexpected end of save data"),
+            PersistenceError::TrailingBytes => write!(f, "Unexpected trailing save data"),
+            PersistenceError::InvalidLength => write!(f, "Invalid length in save data"),
+            PersistenceError::InvalidUtf8 => write!(f, "Invalid UTF-8 string in save data"),
+            PersistenceError::InvalidTag { kind, tag } => write!(f, "Invalid {kind} tag {tag}"),
+            PersistenceError::InvalidResources(error) => {
+                write!(f, "Invalid resource definitions: {error}")
+            }
+            PersistenceError::InvalidSettings(error) => write!(f, "Invalid settings: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for PersistenceError {}
+
+impl From<std::io::Error> for PersistenceError {
+    fn from(error: std::io::Error) -> Self {
+        PersistenceError::Io(error)
+    }
+}
+
+/// A decoded save file: the format version and its named, independently encoded sections.
+#[derive(Debug, Default, Clone)]
+pub struct SaveFile {
+    pub version: u32,
+    pub sections: BTreeMap<String, Vec<u8>>,
+}
+
+impl SaveFile {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = Writer::new();
+        writer.write_bytes(&MAGIC);
+        writer.write(&self.version);
+        writer.write(&self.sections);
+        writer.into_bytes()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PersistenceError> {
+        let mut reader = Reader::new(bytes);
+        if reader.read_array::<4>()? != MAGIC {
+            return Err(PersistenceError::InvalidMagic);
+        }
+
+        let save = Self {
+            version: reader.read()?,
+            sections: reader.read()?,
+        };
+        if !reader.is_empty() {
+            return Err(PersistenceError::TrailingBytes);
+        }
+        Ok(save)
+    }
+
+    pub fn set_section<T: Encode + ?Sized>(&mut self, name: &str, value: &T) {
+        let mut writer = Writer::new();
+        writer.write(value);
+        self.sections.insert(name.to_string(), writer.into_bytes());
+    }
+
+    pub fn section(&self, name: &str) -> Result<Reader<'_>, PersistenceError> {
+        self.sections
+            .get(name)
+            .map(|bytes| Reader::new(bytes))
+            .ok_or_else(|| PersistenceError::MissingSection(name.to_string()))
+    }
+}
+
+impl SimState {
+    pub fn save_to(&self, path: impl AsRef<Path>) -> Result<(), PersistenceError> {
+        std::fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+
+    pub fn load_from(path: impl AsRef<Path>) -> Result<Self, PersistenceError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_save_file().to_bytes()
+    }
+
+    /// Stable hash of the whole state, computed over its canonical save encoding.
+    /// This encodes the entire state, so it should only run every `checksum_interval` ticks.
+    pub fn checksum(&self) -> u64 {
+        let mut hasher = RapidHasher::new(0);
+        let mut writer = Writer::new();
+        self.for_each_section(|name, value| {
+            writer.clear();
+            writer.write(value);
+            hasher.write_u64(name.len() as u64);
+            hasher.write(name.as_bytes());
+            hasher.write_u64(writer.as_bytes().len() as u64);
+            hasher.write(writer.as_bytes());
+        });
+        hasher.finish()
+    }
+
+    /// Older save files are migrated to the current format version before decoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PersistenceError> {
+        let mut save = SaveFile::from_bytes(bytes)?;
+        migration::migrate(&mut save)?;
+        Self::from_save_file(&save)
+    }
+
+    pub fn to_save_file(&self) -> SaveFile {
+        let mut save = SaveFile {
+            version: FORMAT_VERSION,
+            ..Default::default()
+        };
+        self.for_each_section(|name, value| save.set_section(name, value));
+        save
+    }
+
+    /// Asteroids are regenerated from the seed, so only discovered and depleted ones are stored.
+    fn for_each_section(&self, mut visit: impl FnMut(&str, &dyn Encode)) {
+        visit(
+            SECTION_WORLD,
+            &(
+                (self.seed, self.tick),
+                (
+                    self.next_ship_id,
+                    (self.next_human_id, self.next_lineage_id),
+                ),
+            ),
+        );
+        visit(SECTION_SETTINGS, &self.settings);
+        visit(SECTION_RESOURCES, &self.resource_types);
+        visit(
+            SECTION_ASTEROIDS,
+            &(&self.discovered_asteroids, &self.depleted_asteroids),
+        );
+        visit(SECTION_EXPLORED, &self.explored);
+        visit(SECTION_COLONIES, &self.colonies);
+        visit(
+            SECTION_SHIPS,
+            &(&self.scouts, (&self.miners, &self.haulers)),
+        );
+        visit(SECTION_LOGISTICS, &(&self.depots, &self.transport_requests));
+    }
+
+    pub fn from_save_file(save: &SaveFile) -> Result<Self, PersistenceError> {
+        if save.version != FORMAT_VERSION {
+            return Err(PersistenceError::UnsupportedVersion(save.version));
+        }
+
+        let ((seed, tick), (next_ship_id, (next_human_id, next_lineage_id))) =
+            read_section(save, SECTION_WORLD)?;
+        let settings: SimulationSettings = read_section(save, SECTION_SETTINGS)?;
+        settings
+            .validate()
+            .map_err(PersistenceError::InvalidSettings)?;
+        let resource_types = read_section(save, SECTION_RESOURCES)?;
+        let (discovered_asteroids, depleted_asteroids) = read_section(save, SECTION_ASTEROIDS)?;
+        let explored = read_section(save, SECTION_EXPLORED)?;
+        let colonies = read_section(save, SECTION_COLONIES)?;
+        let (scouts, (miners, haulers)) = read_section(save, SECTION_SHIPS)?;
+        let (depots, transport_requests) = read_section(save, SECTION_LOGISTICS)?;
+
+        let mut state = Self {
+            seed,
+            tick,
+            settings,
+            resource_types,
+            discovered_asteroids,
+            depleted_asteroids,
+            explored,
+            colonies,
+            scouts,
+            miners,
+            haulers,
+            depots,
+            transport_requests,
+            asteroid_index: Default::default(),
+            colony_index: Default::default(),
+            depot_index: Default::default(),
+            next_ship_id,
+            next_human_id,
+            next_lineage_id,
+        };
+        state.rebuild_indices();
+        Ok(state)
+    }
+}
+
+pub fn read_section<T: Decode>(save: &SaveFile, name: &str) -> Result<T, PersistenceError> {
+    let mut reader = save.section(name)?;
+    let value = reader.read()?;
+    if !reader.is_empty() {
+        return Err(PersistenceError::TrailingBytes);
+    }
+    Ok(value)
+}
+
+impl<T: Encode + ?Sized> Encode for &T {
+    fn encode(&self, writer: &mut Writer) {
+        (**self).encode(writer);
+    }
+}
+
+/// Implements the codec for a struct by its fields, new fields must be appended at the end.
+macro_rules! impl_struct_codec {
+    ($ty:ty { $($field:ident),* $(,)? }) => {
+        impl Encode for $ty {
+            fn encode(&self, writer: &mut Writer) {
+                $(writer.write(&self.$field);)*
+            }
+        }
+
+        impl Decode for $ty {
+            fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+                Ok(Self {
+                    $($field: reader.read()?,)*
+                })
+            }
+        }
+    };
+}
+
+/// Implements the codec for a newtype id.
+macro_rules! impl_id_codec {
+    ($($ty:ident),*) => {
+        $(
+            impl Encode for $ty {
+                fn encode(&self, writer: &mut Writer) {
+                    writer.write(&self.0);
+                }
+            }
+
+            impl Decode for $ty {
+                fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+                    Ok($ty(reader.read()?))
+                }
+            }
+        )*
+    };
+}
+
+impl_id_codec!(ShipId, HumanId, LineageId);
+
+impl_struct_codec!(SimulationSettings {
+    tps,
+    fps,
+    visible_update_cooldown,
+    max_asteroid_resource_amount,
+    max_asteroid_scale,
+    asteroid_density,
+    scout_speed,
+    scout_sensor_radius,
+    scout_range,
+    scout_launch_interval,
+    max_scouts_per_colony,
+    miner_speed,
+    miner_range,
+    miner_capacity,
+    mining_rate,
+    miner_launch_interval,
+    max_miners_per_colony,
+    depot_distance,
+    hauler_speed,
+    hauler_capacity,
+    max_haulers_per_colony,
+    logistics_interval,
+    colony_stock_target,
+    min_transport_amount,
+    colony_founders,
+    human_base_lifespan,
+    population_interval,
+    reproduction_ice_cost,
+    reproduction_ice_reserve,
+    mutation_rate,
+    mutation_strength,
+    expansion_interval,
+    colony_founding_iron,
+    colony_founding_ice,
+    colony_settlers,
+    colony_founding_range,
+    colony_min_spacing,
+    colony_site_radius,
+    colony_initial_hulls,
+    colony_settler_hulls,
+    ice_upkeep_per_human,
+    upgrade_mining_bonus,
+    recipes,
+    checksum_interval,
+    density_noise_scale,
+    density_noise_octaves,
+    density_void_threshold,
+    resource_noise_scale,
+    resource_noise_contrast,
+    cluster_spacing,
+    cluster_chance,
+    cluster_min_radius,
+    cluster_max_radius,
+    cluster_density,
+    belt_spacing,
+    belt_chance,
+    belt_min_radius,
+    belt_max_radius,
+    belt_width,
+    belt_density,
+    mixed_compositions,
+});
+
+impl_struct_codec!(Recipe {
+    name,
+    inputs,
+    output,
+    output_amount,
+    max_stock,
+});
+
+impl_struct_codec!(ResourceBag { amounts });
+
+impl_struct_codec!(ResourceDefinition {
+    name,
+    color,
+    rarity,
+    value,
+    density,
+});
+
+impl_struct_codec!(RecordedCommand { tick, command });
+
+impl_struct_codec!(Human {
+    id,
+    lineage,
+    generation,
+    genome,
+    age,
+});
+
+impl_struct_codec!(Scout {
+    home,
+    pilot,
+    position,
+    heading,
+    traveled,
+    returning,
+});
+
+impl_struct_codec!(Miner {
+    home,
+    pilot,
+    position,
+    cargo,
+    task,
+});
+
+impl_struct_codec!(Hauler {
+    home,
+    pilot,
+    position,
+    cargo,
+    task,
+});
+
+impl_struct_codec!(Depot { resources });
+
+impl_struct_codec!(TransportRequest {
+    colony,
+    resource,
+    amount,
+    priority,
+});
+
+impl Encode for Colony {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write(&self.resources);
+        writer.write(&self.population);
+        writer.write(&self.products);
+    }
+}
+
+impl Decode for Colony {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        Ok(Self {
+            resources: reader.read()?,
+            population: reader.read()?,
+            products: reader.read()?,
+            production: ProductionRates::default(),
+        })
+    }
+}
+
+impl Encode for Genome {
+    fn encode(&self, writer: &mut Writer) {
+        for gene in Gene::ALL {
+            writer.write(&self.get(gene));
+        }
+    }
+}
+
+impl Decode for Genome {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        let mut values = [0.0f32; Gene::ALL.len()];
+        for value in values.iter_mut() {
+            *value = reader.read()?;
+        }
+        Ok(Genome::from_fn(|gene| values[gene as usize]))
+    }
+}
+
+/// Implements the codec for a fieldless enum as a single tag byte.
+macro_rules! impl_tag_codec {
+    ($ty:ident { $($variant:ident = $tag:literal),* $(,)? }) => {
+        impl Encode for $ty {
+            fn encode(&self, writer: &mut Writer) {
+                let tag: u8 = match self {
+                    $($ty::$variant => $tag,)*
+                };
+                writer.write(&tag);
+            }
+        }
+
+        impl Decode for $ty {
+            fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+                match reader.read::<u8>()? {
+                    $($tag => Ok($ty::$variant),)*
+                    tag => Err(PersistenceError::InvalidTag {
+                        kind: stringify!($ty),
+                        tag,
+                    }),
+                }
+            }
+        }
+    };
+}
+
+impl_tag_codec!(Layer {
+    Asteroids = 0,
+    Colonies = 1,
+    Ships = 2,
+    Fog = 3,
+    Debug = 4,
+});
+
+impl Encode for ResourceType {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write(&self.0);
+    }
+}
+
+impl Decode for ResourceType {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        Ok(ResourceType(reader.read()?))
+    }
+}
+
+impl Encode for RGBA {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write(&((self.r(), self.g()), (self.b(), self.a())));
+    }
+}
+
+impl Decode for RGBA {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        let ((r, g), (b, a)) = reader.read()?;
+        Ok(RGBA::new(r, g, b, a))
+    }
+}
+
+impl_struct_codec!(ExploredMap { chunks });
+
+impl Encode for ResourceRegistry {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write(&self.definitions().to_vec());
+    }
+}
+
+impl Decode for ResourceRegistry {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        ResourceRegistry::new(reader.read()?).map_err(PersistenceError::InvalidResources)
+    }
+}
+
+impl_tag_codec!(Product {
+    Hull = 0,
+    Upgrade = 1,
+});
+
+impl Encode for MinerTask {
+    fn encode(&self, writer: &mut Writer) {
+        match self {
+            MinerTask::Travelling(target) => (0u8, *target).encode(writer),
+            MinerTask::Mining(target) => (1u8, *target).encode(writer),
+            MinerTask::Delivering(depot) => (2u8, *depot).encode(writer),
+            MinerTask::Returning => 3u8.encode(writer),
+        }
+    }
+}
+
+impl Decode for MinerTask {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        match reader.read::<u8>()? {
+            0 => Ok(MinerTask::Travelling(reader.read()?)),
+            1 => Ok(MinerTask::Mining(reader.read()?)),
+            2 => Ok(MinerTask::Delivering(reader.read()?)),
+            3 => Ok(MinerTask::Returning),
+            tag => Err(PersistenceError::InvalidTag {
+                kind: "MinerTask",
+                tag,
+            }),
+        }
+    }
+}
+
+impl Encode for HaulerTask {
+    fn encode(&self, writer: &mut Writer) {
+        match self {
+            HaulerTask::Pickup(source, request) => (0u8, (*source, *request)).encode(writer),
+            HaulerTask::Deliver(request) => (1u8, *request).encode(writer),
+            HaulerTask::Returning => 2u8.encode(writer),
+        }
+    }
+}
+
+impl Decode for HaulerTask {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        match reader.read::<u8>()? {
+            0 => Ok(HaulerTask::Pickup(reader.read()?, reader.read()?)),
+            1 => Ok(HaulerTask::Deliver(reader.read()?)),
+            2 => Ok(HaulerTask::Returning),
+            tag => Err(PersistenceError::InvalidTag {
+                kind: "HaulerTask",
+                tag,
+            }),
+        }
+    }
+}
+
+impl Encode for Stockpile {
+    fn encode(&self, writer: &mut Writer) {
+        match self {
+            Stockpile::Colony(point) => (0u8, *point).encode(writer),
+            Stockpile::Depot(point) => (1u8, *point).encode(writer),
+        }
+    }
+}
+
+impl Decode for Stockpile {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        match reader.read::<u8>()? {
+            0 => Ok(Stockpile::Colony(reader.read()?)),
+            1 => Ok(Stockpile::Depot(reader.read()?)),
+            tag => Err(PersistenceError::InvalidTag {
+                kind: "Stockpile",
+                tag,
+            }),
+        }
+    }
+}
+
+impl Encode for SimCommand {
+    fn encode(&self, writer: &mut Writer) {
+        match self {
+            SimCommand::Clear => 0u8.encode(writer),
+            SimCommand::Pause => 1u8.encode(writer),
+            SimCommand::Resume => 2u8.encode(writer),
+            SimCommand::TogglePause => 3u8.encode(writer),
+            SimCommand::Shutdown => 4u8.encode(writer),
+            SimCommand::SetVisibleRect(rect) => (5u8, rect).encode(writer),
+            SimCommand::SetScreenSize(size) => (6u8, size).encode(writer),
+            SimCommand::ScoutArea(area) => (7u8, area).encode(writer),
+            SimCommand::FoundColony(point) => (8u8, point).encode(writer),
+            SimCommand::Save(path) => (9u8, path).encode(writer),
+            SimCommand::Load(path) => (10u8, path).encode(writer),
+            SimCommand::StartRecording => 11u8.encode(writer),
+            SimCommand::StopRecording(path) => (12u8, path).encode(writer),
+            SimCommand::SetTimeScale(scale) => (13u8, scale).encode(writer),
+            SimCommand::StepTicks(ticks) => (14u8, ticks).encode(writer),
+            SimCommand::RunUntilTick(tick) => (15u8, tick).encode(writer),
+            SimCommand::UpdateSettings(settings) => (16u8, settings.as_ref()).encode(writer),
+            SimCommand::SetLayerVisibility(layer, visible) => {
+                (17u8, (layer, visible)).encode(writer)
+            }
+        }
+    }
+}
+
+impl Decode for SimCommand {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        match reader.read::<u8>()? {
+            0 => Ok(SimCommand::Clear),
+            1 => Ok(SimCommand::Pause),
+            2 => Ok(SimCommand::Resume),
+            3 => Ok(SimCommand::TogglePause),
+            4 => Ok(SimCommand::Shutdown),
+            5 => Ok(SimCommand::SetVisibleRect(reader.read()?)),
+            6 => Ok(SimCommand::SetScreenSize(reader.read()?)),
+            7 => Ok(SimCommand::ScoutArea(reader.read()?)),
+            8 => Ok(SimCommand::FoundColony(reader.read()?)),
+            9 => Ok(SimCommand::Save(reader.read()?)),
+            10 => Ok(SimCommand::Load(reader.read()?)),
+            11 => Ok(SimCommand::StartRecording),
+            12 => Ok(SimCommand::StopRecording(reader.read()?)),
+            13 => Ok(SimCommand::SetTimeScale(reader.read()?)),
+            14 => Ok(SimCommand::StepTicks(reader.read()?)),
+            15 => Ok(SimCommand::RunUntilTick(reader.read()?)),
+            16 => Ok(SimCommand::UpdateSettings(Box::new(reader.read()?))),
+            17 => {
+                let (layer, visible) = reader.read()?;
+                Ok(SimCommand::SetLayerVisibility(layer, visible))
+            }
+            tag => Err(PersistenceError::InvalidTag {
+                kind: "SimCommand",
+                tag,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::point::Point;
+    use crate::simulation::Simulation;
+
+    fn simulated_state(ticks: u64) -> SimState {
+        let state = SimState::new_with_seed(2).with_colony(Point::new(1000, 700));
+        let mut simulation = Simulation::new(state);
+        simulation.step(ticks);
+        simulation.state().clone()
+    }
+
+    #[test]
+    fn round_trip_preserves_state() {
+        let state = simulated_state(2000);
+        let bytes = state.to_bytes();
+
+        let loaded = SimState::from_bytes(&bytes).unwrap();
+        assert_eq!(loaded.checksum(), state.checksum());
+        assert_eq!(loaded.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn truncated_input_is_unexpected_eof() {
+        let bytes = simulated_state(100).to_bytes();
+        for len in [2, 8, bytes.len() / 2, bytes.len() - 1] {
+            assert!(matches!(
+                SimState::from_bytes(&bytes[..len]),
+                Err(PersistenceError::UnexpectedEof)
+            ));
+        }
+    }
+
+    #[test]
+    fn unknown_tag_is_invalid_tag() {
+        let mut reader = Reader::new(&[200]);
+        assert!(matches!(
+            reader.read::<SimCommand>(),
+            Err(PersistenceError::InvalidTag {
+                kind: "SimCommand",
+                tag: 200
+            })
+        ));
+    }
+
+    #[test]
+    fn invalid_settings_are_rejected() {
+        let mut save = SimState::new_with_seed(2).to_save_file();
+        let settings = SimulationSettings {
+            tps: 0,
+            ..Default::default()
+        };
+        save.set_section(SECTION_SETTINGS, &settings);
+
+        assert!(matches!(
+            SimState::from_save_file(&save),
+            Err(PersistenceError::InvalidSettings(SettingsError::ZeroTps))
+        ));
+    }
+
+    #[test]
+    fn overflowing_duration_nanos_are_rejected() {
+        let mut writer = Writer::new();
+        writer.write(&(u64::MAX, 1_000_000_000u32));
+        let bytes = writer.into_bytes();
+
+        assert!(matches!(
+            Reader::new(&bytes).read::<std::time::Duration>(),
+            Err(PersistenceError::InvalidLength)
+        ));
+    }
+}
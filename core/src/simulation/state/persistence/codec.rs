@@ -0,0 +1,390 @@
+use crate::math::area::Area;
+use crate::math::circle::Circle;
+use crate::math::point::Point;
+use crate::math::rect::Rect;
+use crate::math::size::Size;
+use crate::simulation::state::persistence::PersistenceError;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Debug, Default)]
+pub struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn write<T: Encode + ?Sized>(&mut self, value: &T) {
+        value.encode(self);
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Empties the writer, keeping its allocation for reuse.
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    pub fn read<T: Decode>(&mut self) -> Result<T, PersistenceError> {
+        T::decode(self)
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], PersistenceError> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(PersistenceError::UnexpectedEof)?;
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N], PersistenceError> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.read_bytes(N)?);
+        Ok(array)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.position >= self.bytes.len()
+    }
+}
+
+pub trait Encode {
+    fn encode(&self, writer: &mut Writer);
+}
+
+pub trait Decode: Sized {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError>;
+}
+
+macro_rules! impl_codec_le {
+    ($($ty:ty),*) => {
+        $(
+            impl Encode for $ty {
+                fn encode(&self, writer: &mut Writer) {
+                    writer.write_bytes(&self.to_le_bytes());
+                }
+            }
+
+            impl Decode for $ty {
+                fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+                    Ok(Self::from_le_bytes(reader.read_array()?))
+                }
+            }
+        )*
+    };
+}
+
+impl_codec_le!(u8, u16, u32, u64, i64, f32, f64);
+
+impl Encode for usize {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write(&(*self as u64));
+    }
+}
+
+impl Decode for usize {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        usize::try_from(reader.read::<u64>()?).map_err(|_| PersistenceError::InvalidLength)
+    }
+}
+
+impl Encode for bool {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write(&(*self as u8));
+    }
+}
+
+impl Decode for bool {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        match reader.read::<u8>()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => Err(PersistenceError::InvalidTag { kind: "bool", tag }),
+        }
+    }
+}
+
+impl Encode for str {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write(&self.len());
+        writer.write_bytes(self.as_bytes());
+    }
+}
+
+impl Encode for String {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write(self.as_str());
+    }
+}
+
+impl Decode for String {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        let len = reader.read()?;
+        let bytes = reader.read_bytes(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| PersistenceError::InvalidUtf8)
+    }
+}
+
+impl Encode for PathBuf {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write(self.to_string_lossy().as_ref());
+    }
+}
+
+impl Decode for PathBuf {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        Ok(PathBuf::from(reader.read::<String>()?))
+    }
+}
+
+impl Encode for Duration {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write(&self.as_secs());
+        writer.write(&self.subsec_nanos());
+    }
+}
+
+impl Decode for Duration {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        let secs = reader.read()?;
+        let nanos: u32 = reader.read()?;
+        // `Duration::new` would carry excess nanos into the seconds and panic on overflow.
+        if nanos >= 1_000_000_000 {
+            return Err(PersistenceError::InvalidLength);
+        }
+        Ok(Duration::new(secs, nanos))
+    }
+}
+
+impl<N: Encode> Encode for Point<N> {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write(&self.x);
+        writer.write(&self.y);
+    }
+}
+
+impl<N: Decode> Decode for Point<N> {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        Ok(Point::new(reader.read()?, reader.read()?))
+    }
+}
+
+impl<N: Encode> Encode for Size<N> {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write(&self.width);
+        writer.write(&self.height);
+    }
+}
+
+impl<N: Decode> Decode for Size<N> {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        Ok(Size {
+            width: reader.read()?,
+            height: reader.read()?,
+        })
+    }
+}
+
+impl<N: Encode> Encode for Rect<N> {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write(&self.min);
+        writer.write(&self.max);
+    }
+}
+
+impl<N: Decode> Decode for Rect<N> {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        Ok(Rect::new(reader.read()?, reader.read()?))
+    }
+}
+
+impl<N: Encode> Encode for Circle<N> {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write(self.center());
+        writer.write(self.radius());
+    }
+}
+
+impl<N: Decode> Decode for Circle<N> {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        Ok(Circle::new(reader.read()?, reader.read()?))
+    }
+}
+
+impl<N: Encode> Encode for Area<N> {
+    fn encode(&self, writer: &mut Writer) {
+        match self {
+            Area::Circle(circle) => {
+                writer.write(&0u8);
+                writer.write(circle);
+            }
+            Area::Rect(rect) => {
+                writer.write(&1u8);
+                writer.write(rect);
+            }
+        }
+    }
+}
+
+impl<N: Decode> Decode for Area<N> {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        match reader.read::<u8>()? {
+            0 => Ok(Area::Circle(reader.read()?)),
+            1 => Ok(Area::Rect(reader.read()?)),
+            tag => Err(PersistenceError::InvalidTag { kind: "Area", tag }),
+        }
+    }
+}
+
+impl<A: Encode, B: Encode> Encode for (A, B) {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write(&self.0);
+        writer.write(&self.1);
+    }
+}
+
+impl<A: Decode, B: Decode> Decode for (A, B) {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        Ok((reader.read()?, reader.read()?))
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode(&self, writer: &mut Writer) {
+        match self {
+            None => writer.write(&0u8),
+            Some(value) => {
+                writer.write(&1u8);
+                writer.write(value);
+            }
+        }
+    }
+}
+
+impl<T: Decode> Decode for Option<T> {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        match reader.read::<u8>()? {
+            0 => Ok(None),
+            1 => Ok(Some(reader.read()?)),
+            tag => Err(PersistenceError::InvalidTag {
+                kind: "Option",
+                tag,
+            }),
+        }
+    }
+}
+
+fn encode_iter<'a, T: Encode + 'a>(
+    writer: &mut Writer,
+    len: usize,
+    items: impl Iterator<Item = &'a T>,
+) {
+    writer.write(&len);
+    for item in items {
+        writer.write(item);
+    }
+}
+
+fn decode_iter<T: Decode, C: FromIterator<T>>(reader: &mut Reader) -> Result<C, PersistenceError> {
+    let len: usize = reader.read()?;
+    (0..len).map(|_| reader.read()).collect()
+}
+
+impl<T: Encode> Encode for Vec<T> {
+    fn encode(&self, writer: &mut Writer) {
+        encode_iter(writer, self.len(), self.iter());
+    }
+}
+
+impl<T: Decode> Decode for Vec<T> {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        decode_iter(reader)
+    }
+}
+
+/// Fixed-size arrays are written without a length.
+impl<T: Encode, const N: usize> Encode for [T; N] {
+    fn encode(&self, writer: &mut Writer) {
+        for value in self {
+            writer.write(value);
+        }
+    }
+}
+
+impl<T: Decode + Default + Copy, const N: usize> Decode for [T; N] {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        let mut values = [T::default(); N];
+        for value in &mut values {
+            *value = reader.read()?;
+        }
+        Ok(values)
+    }
+}
+
+impl<T: Encode> Encode for Box<T> {
+    fn encode(&self, writer: &mut Writer) {
+        (**self).encode(writer);
+    }
+}
+
+impl<T: Decode> Decode for Box<T> {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        Ok(Box::new(reader.read()?))
+    }
+}
+
+impl<T: Encode> Encode for BTreeSet<T> {
+    fn encode(&self, writer: &mut Writer) {
+        encode_iter(writer, self.len(), self.iter());
+    }
+}
+
+impl<T: Decode + Ord> Decode for BTreeSet<T> {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        decode_iter(reader)
+    }
+}
+
+impl<K: Encode, V: Encode> Encode for BTreeMap<K, V> {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write(&self.len());
+        for (key, value) in self {
+            writer.write(key);
+            writer.write(value);
+        }
+    }
+}
+
+impl<K: Decode + Ord, V: Decode> Decode for BTreeMap<K, V> {
+    fn decode(reader: &mut Reader) -> Result<Self, PersistenceError> {
+        decode_iter(reader)
+    }
+}
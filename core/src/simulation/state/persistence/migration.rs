@@ -0,0 +1,164 @@
+use crate::simulation::state::explored::ExploredMap;
+use crate::simulation::state::persistence::codec::{Encode, Writer};
+use crate::simulation::state::persistence::{
+    PersistenceError, SaveFile, FORMAT_VERSION, SECTION_EXPLORED, SECTION_RESOURCES,
+    SECTION_SETTINGS,
+};
+use crate::simulation::state::resource::ResourceRegistry;
+use crate::simulation::state::settings::SimulationSettings;
+
+/// Upgrades a save file from one format version to the next.
+pub type Migration = fn(&mut SaveFile) -> Result<(), PersistenceError>;
+
+/// The migration at index `i` upgrades version `i + 1` to version `i + 2`.
+/// Every bump of `FORMAT_VERSION` has to append a migration here.
+const MIGRATIONS: &[Migration] = &[
+    add_checksum_interval,
+    add_noise_settings,
+    add_structure_settings,
+    add_resource_types,
+    add_explored,
+    add_mixed_compositions,
+];
+
+const _: () = assert!(MIGRATIONS.len() as u32 + 1 == FORMAT_VERSION);
+
+fn add_checksum_interval(save: &mut SaveFile) -> Result<(), PersistenceError> {
+    let settings = SimulationSettings::default();
+    save.append_to_section(SECTION_SETTINGS, &settings.checksum_interval)
+}
+
+/// Worlds saved before the noise fields were generated uniformly, so they keep the noise
+/// disabled to stay consistent with the asteroids already discovered in them.
+fn add_noise_settings(save: &mut SaveFile) -> Result<(), PersistenceError> {
+    let settings = SimulationSettings::default();
+    save.append_to_section(SECTION_SETTINGS, &0.0f32)?;
+    save.append_to_section(SECTION_SETTINGS, &settings.density_noise_octaves)?;
+    save.append_to_section(SECTION_SETTINGS, &settings.density_void_threshold)?;
+    save.append_to_section(SECTION_SETTINGS, &0.0f32)?;
+    save.append_to_section(SECTION_SETTINGS, &settings.resource_noise_contrast)
+}
+
+/// Like the noise, clusters and belts stay disabled in worlds saved before they existed.
+fn add_structure_settings(save: &mut SaveFile) -> Result<(), PersistenceError> {
+    let settings = SimulationSettings::default();
+    save.append_to_section(SECTION_SETTINGS, &settings.cluster_spacing)?;
+    save.append_to_section(SECTION_SETTINGS, &0.0f64)?;
+    save.append_to_section(SECTION_SETTINGS, &settings.cluster_min_radius)?;
+    save.append_to_section(SECTION_SETTINGS, &settings.cluster_max_radius)?;
+    save.append_to_section(SECTION_SETTINGS, &settings.cluster_density)?;
+    save.append_to_section(SECTION_SETTINGS, &settings.belt_spacing)?;
+    save.append_to_section(SECTION_SETTINGS, &0.0f64)?;
+    save.append_to_section(SECTION_SETTINGS, &settings.belt_min_radius)?;
+    save.append_to_section(SECTION_SETTINGS, &settings.belt_max_radius)?;
+    save.append_to_section(SECTION_SETTINGS, &settings.belt_width)?;
+    save.append_to_section(SECTION_SETTINGS, &settings.belt_density)
+}
+
+/// Worlds from before the registry was data-driven used the built-in resources.
+fn add_resource_types(save: &mut SaveFile) -> Result<(), PersistenceError> {
+    save.set_section(SECTION_RESOURCES, &ResourceRegistry::default());
+    Ok(())
+}
+
+/// Older worlds did not record scanned cells, so all of space starts out unexplored.
+fn add_explored(save: &mut SaveFile) -> Result<(), PersistenceError> {
+    save.set_section(SECTION_EXPLORED, &ExploredMap::default());
+    Ok(())
+}
+
+/// Asteroids used to be made of a single resource, discovered ones would change if they were mixed.
+fn add_mixed_compositions(save: &mut SaveFile) -> Result<(), PersistenceError> {
+    save.append_to_section(SECTION_SETTINGS, &false)
+}
+
+/// Applies all migrations needed to bring a save file up to the current format version.
+pub fn migrate(save: &mut SaveFile) -> Result<(), PersistenceError> {
+    if save.version > FORMAT_VERSION {
+        return Err(PersistenceError::NewerVersion {
+            found: save.version,
+            supported: FORMAT_VERSION,
+        });
+    }
+    if save.version == 0 {
+        return Err(PersistenceError::UnsupportedVersion(save.version));
+    }
+
+    for migration in &MIGRATIONS[(save.version - 1) as usize..] {
+        migration(save)?;
+        save.version += 1;
+    }
+    Ok(())
+}
+
+impl SaveFile {
+    /// Appends a value to the end of an existing section, for fields added at the end of a struct.
+    pub fn append_to_section<T: Encode + ?Sized>(
+        &mut self,
+        name: &str,
+        value: &T,
+    ) -> Result<(), PersistenceError> {
+        let section = self
+            .sections
+            .get_mut(name)
+            .ok_or_else(|| PersistenceError::MissingSection(name.to_string()))?;
+
+        let mut writer = Writer::new();
+        writer.write(value);
+        section.extend_from_slice(&writer.into_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::point::Point;
+    use crate::simulation::state::resource::ResourceType;
+    use crate::simulation::state::SimState;
+
+    /// A world saved with the first format version, after 2000 ticks of seed 2.
+    const SAVE_V1: &[u8] = include_bytes!("../../../../tests/fixtures/save_v1.bin");
+
+    #[test]
+    fn migrates_v1_to_current_version() {
+        let mut save = SaveFile::from_bytes(SAVE_V1).unwrap();
+        assert_eq!(save.version, 1);
+
+        migrate(&mut save).unwrap();
+        assert_eq!(save.version, FORMAT_VERSION);
+
+        let state = SimState::from_save_file(&save).unwrap();
+        assert_eq!(state.seed(), 2);
+        assert_eq!(state.tick(), 2000);
+        assert_eq!(state.colonies.len(), 1);
+        // Settings that didn't exist yet keep the features disabled.
+        assert_eq!(state.settings.density_noise_scale, 0.0);
+        assert_eq!(state.settings.cluster_chance, 0.0);
+        assert!(state.explored.chunks.is_empty());
+
+        // Discovered asteroids keep the single resource they were made of in version 1.
+        for (point, resource) in [
+            (Point::new(1007, 716), ResourceType::ICE),
+            (Point::new(1023, 742), ResourceType::GOLD),
+            (Point::new(1210, 650), ResourceType::IRON),
+        ] {
+            let mined = state.composition_at(point).unwrap().split(1.0);
+            assert_eq!(mined.total(resource), 1.0);
+            assert_eq!(mined.total_amount(), 1.0);
+        }
+        assert_eq!(state.discovered_asteroids[&Point::new(1007, 716)], 49.50109);
+    }
+
+    #[test]
+    fn rejects_newer_version() {
+        let mut save = SimState::new_with_seed(2).to_save_file();
+        save.version = FORMAT_VERSION + 1;
+
+        assert!(matches!(
+            migrate(&mut save),
+            Err(PersistenceError::NewerVersion { found, supported })
+                if found == FORMAT_VERSION + 1 && supported == FORMAT_VERSION
+        ));
+    }
+}
@@ -0,0 +1,60 @@
+use crate::simulation::state::resource::ResourceType;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Product {
+    Hull,
+    Upgrade,
+}
+
+impl Display for Product {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Product::Hull => write!(f, "Hull"),
+            Product::Upgrade => write!(f, "Upgrade"),
+        }
+    }
+}
+
+/// Converts resources into a product every tick while the colony has the inputs in stock.
+#[derive(Debug, Clone)]
+pub struct Recipe {
+    pub name: String,
+    pub inputs: Vec<(ResourceType, f32)>,
+    pub output: Product,
+    pub output_amount: f32,
+    /// The recipe idles once the colony holds this much of the output.
+    pub max_stock: f32,
+}
+
+impl Recipe {
+    pub fn default_recipes() -> Vec<Recipe> {
+        vec![
+            Recipe {
+                name: "Hull Assembly".to_string(),
+                inputs: vec![(ResourceType::IRON, 0.2)],
+                output: Product::Hull,
+                output_amount: 0.002,
+                max_stock: 12.0,
+            },
+            Recipe {
+                name: "Upgrade Fabrication".to_string(),
+                inputs: vec![(ResourceType::GOLD, 0.1)],
+                output: Product::Upgrade,
+                output_amount: 0.001,
+                max_stock: 10.0,
+            },
+        ]
+    }
+}
+
+/// What a colony consumed and produced during the last tick.
+#[derive(Debug, Default, Clone)]
+pub struct ProductionRates {
+    pub consumed: BTreeMap<ResourceType, f32>,
+    pub produced: BTreeMap<Product, f32>,
+    pub starving: bool,
+    /// Ice upkeep the colony couldn't cover.
+    pub ice_shortage: f32,
+}
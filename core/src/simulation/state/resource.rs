@@ -1,18 +1,275 @@
 use crate::math::rgba::RGBA;
+use crate::simulation::state::resource_bag::ResourceBag;
+use std::fmt::Display;
+use std::path::Path;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
-pub enum ResourceType {
-    Ice,
-    Iron,
-    Gold,
+/// Index of a resource type in the [`ResourceRegistry`] of the world.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub struct ResourceType(pub u8);
+
+impl ResourceType {
+    pub const ICE: ResourceType = ResourceType(0);
+    pub const IRON: ResourceType = ResourceType(1);
+    pub const GOLD: ResourceType = ResourceType(2);
+
+    /// Resources colonies depend on, every registry starts with them in this order.
+    pub const BUILTIN: [(ResourceType, &'static str); 3] = [
+        (ResourceType::ICE, "Ice"),
+        (ResourceType::IRON, "Iron"),
+        (ResourceType::GOLD, "Gold"),
+    ];
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceDefinition {
+    pub name: String,
+    pub color: RGBA,
+    /// Relative weight when rolling what an asteroid is made of.
+    pub rarity: f64,
+    pub value: f32,
+    /// Mass per volume relative to ice.
+    pub density: f32,
+}
+
+const DEFAULT_DEFINITIONS: &str = include_str!("../../../data/resources.txt");
+
+/// The resource types of a world, loaded from a definition file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceRegistry {
+    definitions: Vec<ResourceDefinition>,
+}
+
+impl ResourceRegistry {
+    pub fn new(definitions: Vec<ResourceDefinition>) -> Result<Self, ResourceRegistryError> {
+        if definitions.len() > u8::MAX as usize + 1 {
+            return Err(ResourceRegistryError::TooMany(definitions.len()));
+        }
+        // Colonies eat and build with these by index, so they can't move.
+        for (resource, name) in ResourceType::BUILTIN {
+            let index = resource.0 as usize;
+            if definitions.get(index).is_some_and(|d| d.name == name) {
+                continue;
+            }
+            return Err(match definitions.iter().position(|d| d.name == name) {
+                Some(found) => ResourceRegistryError::MisplacedBuiltin {
+                    name,
+                    expected: index + 1,
+                    found: found + 1,
+                },
+                None => ResourceRegistryError::MissingBuiltin(name),
+            });
+        }
+        for (index, definition) in definitions.iter().enumerate() {
+            if definitions[..index]
+                .iter()
+                .any(|other| other.name == definition.name)
+            {
+                return Err(ResourceRegistryError::DuplicateName(
+                    definition.name.clone(),
+                ));
+            }
+            let valid = definition.rarity.is_finite()
+                && definition.rarity >= 0.0
+                && definition.value.is_finite()
+                && definition.value >= 0.0
+                && definition.density.is_finite()
+                && definition.density > 0.0;
+            if !valid {
+                return Err(ResourceRegistryError::InvalidValue(definition.name.clone()));
+            }
+        }
+        Ok(Self { definitions })
+    }
+
+    /// Parses whitespace separated `name color rarity value density` lines,
+    /// ignoring blank lines and `#` comments.
+    pub fn parse(source: &str) -> Result<Self, ResourceRegistryError> {
+        let mut definitions = Vec::new();
+
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = |message: &str| ResourceRegistryError::Parse {
+                line: index + 1,
+                message: message.to_string(),
+            };
+            let fields: Vec<_> = line.split_whitespace().collect();
+            let [name, color, rarity, value, density] = fields[..] else {
+                return Err(invalid("expected name, color, rarity, value and density"));
+            };
+
+            definitions.push(ResourceDefinition {
+                name: name.to_string(),
+                color: parse_color(color).ok_or_else(|| invalid("invalid color"))?,
+                rarity: rarity.parse().map_err(|_| invalid("invalid rarity"))?,
+                value: value.parse().map_err(|_| invalid("invalid value"))?,
+                density: density.parse().map_err(|_| invalid("invalid density"))?,
+            });
+        }
+
+        Self::new(definitions)
+    }
+
+    pub fn load_from(path: impl AsRef<Path>) -> Result<Self, ResourceRegistryError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn get(&self, resource: ResourceType) -> Option<&ResourceDefinition> {
+        self.definitions.get(resource.0 as usize)
+    }
+
+    pub fn name(&self, resource: ResourceType) -> &str {
+        self.get(resource).map_or("Unknown", |d| d.name.as_str())
+    }
+
+    pub fn color(&self, resource: ResourceType) -> RGBA {
+        self.get(resource).map_or(RGBA::white(), |d| d.color)
+    }
+
+    pub fn types(&self) -> impl Iterator<Item = ResourceType> + '_ {
+        (0..self.definitions.len()).map(|index| ResourceType(index as u8))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (ResourceType, &ResourceDefinition)> + '_ {
+        self.types().zip(&self.definitions)
+    }
+
+    pub fn definitions(&self) -> &[ResourceDefinition] {
+        &self.definitions
+    }
+
+    /// Total worth of the resources in the bag.
+    pub fn value_of(&self, bag: &ResourceBag) -> f32 {
+        bag.iter()
+            .filter_map(|(resource, amount)| Some(self.get(resource)?.value * amount))
+            .sum()
+    }
+}
+
+impl Default for ResourceRegistry {
+    fn default() -> Self {
+        Self::parse(DEFAULT_DEFINITIONS).expect("default resource definitions are valid")
+    }
+}
+
+fn parse_color(value: &str) -> Option<RGBA> {
+    let hex = value.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |index: usize| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok();
+    Some(RGBA::rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+#[derive(Debug)]
+pub enum ResourceRegistryError {
+    Io(std::io::Error),
+    Parse {
+        line: usize,
+        message: String,
+    },
+    MissingBuiltin(&'static str),
+    /// A built-in resource at the wrong position, counting definitions from 1.
+    MisplacedBuiltin {
+        name: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    DuplicateName(String),
+    InvalidValue(String),
+    TooMany(usize),
+}
+
+impl Display for ResourceRegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResourceRegistryError::Io(error) => write!(f, "I/O error: {error}"),
+            ResourceRegistryError::Parse { line, message } => {
+                write!(f, "Line {line}: {message}")
+            }
+            ResourceRegistryError::MissingBuiltin(name) => write!(
+                f,
+                "{name} must be defined, Ice, Iron and Gold have to be the first resources"
+            ),
+            ResourceRegistryError::MisplacedBuiltin {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{name} is resource {found} but must be resource {expected}, \
+                 Ice, Iron and Gold have to be the first resources in this order"
+            ),
+            ResourceRegistryError::DuplicateName(name) => {
+                write!(f, "Resource {name} is defined more than once")
+            }
+            ResourceRegistryError::InvalidValue(name) => write!(
+                f,
+                "Resource {name} needs a non-negative rarity and value and a positive density"
+            ),
+            ResourceRegistryError::TooMany(count) => {
+                write!(
+                    f,
+                    "{count} resource types defined, at most 256 are supported"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ResourceRegistryError {}
+
+impl From<std::io::Error> for ResourceRegistryError {
+    fn from(error: std::io::Error) -> Self {
+        ResourceRegistryError::Io(error)
+    }
 }
 
-impl From<ResourceType> for RGBA {
-    fn from(resource_type: ResourceType) -> Self {
-        match resource_type {
-            ResourceType::Ice => RGBA::rgb(98, 194, 207),
-            ResourceType::Iron => RGBA::rgb(165, 95, 75),
-            ResourceType::Gold => RGBA::rgb(207, 179, 84),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ICE: &str = "Ice #62c2cf 61 1.0 1.0";
+    const IRON: &str = "Iron #a55f4b 30 4.0 8.5";
+    const GOLD: &str = "Gold #cfb354 9 20.0 21.0";
+
+    #[test]
+    fn default_definitions_start_with_builtins() {
+        let registry = ResourceRegistry::default();
+        for (resource, name) in ResourceType::BUILTIN {
+            assert_eq!(registry.name(resource), name);
         }
     }
+
+    #[test]
+    fn extra_resources_follow_builtins() {
+        let source = [ICE, IRON, GOLD, "Platinum #e5e4e2 2 50.0 21.4"].join("\n");
+        let registry = ResourceRegistry::parse(&source).unwrap();
+        assert_eq!(registry.name(ResourceType(3)), "Platinum");
+    }
+
+    #[test]
+    fn reordered_builtins_are_rejected() {
+        let source = [IRON, ICE, GOLD].join("\n");
+        assert!(matches!(
+            ResourceRegistry::parse(&source),
+            Err(ResourceRegistryError::MisplacedBuiltin {
+                name: "Ice",
+                expected: 1,
+                found: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn omitted_builtins_are_rejected() {
+        let source = [ICE, IRON].join("\n");
+        assert!(matches!(
+            ResourceRegistry::parse(&source),
+            Err(ResourceRegistryError::MissingBuiltin("Gold"))
+        ));
+    }
 }
@@ -1,9 +1,9 @@
 use crate::simulation::state::resource::ResourceType;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Default, Clone)]
 pub struct ResourceBag {
-    pub amounts: HashMap<ResourceType, (u64, f32)>,
+    pub amounts: BTreeMap<ResourceType, (u64, f32)>,
 }
 
 impl ResourceBag {
@@ -26,12 +26,17 @@ impl ResourceBag {
 
         let (total, frac) = self.amounts.entry(resource).or_insert((0, 0.0));
 
+        // Removing everything can still borrow: the f32 total may round its fraction up.
         *frac -= amount.fract();
-        *total -= amount as u64;
+        *total = total.saturating_sub(amount as u64);
 
         if *frac < 0.0 {
-            *frac += 1.0;
-            *total -= 1;
+            if *total > 0 {
+                *frac += 1.0;
+                *total -= 1;
+            } else {
+                *frac = 0.0;
+            }
         }
 
         true
@@ -42,4 +47,21 @@ impl ResourceBag {
             .get(&resource)
             .map_or(0.0, |(total, frac)| *total as f32 + frac)
     }
+
+    pub fn total_amount(&self) -> f32 {
+        self.amounts
+            .values()
+            .map(|(total, frac)| *total as f32 + frac)
+            .sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (ResourceType, f32)> + '_ {
+        self.amounts
+            .iter()
+            .map(|(resource, (total, frac))| (*resource, *total as f32 + frac))
+    }
+
+    pub fn clear(&mut self) {
+        self.amounts.clear();
+    }
 }
@@ -0,0 +1,39 @@
+use crate::math::point::Point;
+use crate::simulation::state::human::Human;
+
+#[derive(Debug, Clone)]
+pub struct Scout {
+    pub home: Point<i64>,
+    pub pilot: Human,
+    pub position: Point<f32>,
+    pub heading: Point<f32>,
+    pub traveled: f32,
+    pub returning: bool,
+}
+
+impl Scout {
+    pub fn new(home: Point<i64>, pilot: Human, heading: Point<f32>) -> Self {
+        Self {
+            home,
+            pilot,
+            position: home.to_f32(),
+            heading,
+            traveled: 0.0,
+            returning: false,
+        }
+    }
+
+    /// Advances the scout by one tick, returns true once it is back home.
+    pub fn advance(&mut self, speed: f32, range: f32) -> bool {
+        if self.returning {
+            return self.position.step_towards(self.home.to_f32(), speed);
+        }
+
+        self.position = self.position + self.heading * speed;
+        self.traveled += speed;
+        if self.traveled >= range {
+            self.returning = true;
+        }
+        false
+    }
+}
@@ -1,3 +1,6 @@
+use crate::simulation::procedural::structure::{BeltField, ClusterField};
+use crate::simulation::state::production::Recipe;
+use std::fmt::Display;
 use std::time::Duration;
 
 #[derive(Debug, Clone)]
@@ -8,6 +11,71 @@ pub struct SimulationSettings {
     pub max_asteroid_resource_amount: f32,
     pub max_asteroid_scale: f32,
     pub asteroid_density: f64,
+    pub scout_speed: f32,
+    pub scout_sensor_radius: f32,
+    pub scout_range: f32,
+    pub scout_launch_interval: u64,
+    pub max_scouts_per_colony: usize,
+    pub miner_speed: f32,
+    pub miner_range: f32,
+    pub miner_capacity: f32,
+    pub mining_rate: f32,
+    pub miner_launch_interval: u64,
+    pub max_miners_per_colony: usize,
+    pub depot_distance: f32,
+    pub hauler_speed: f32,
+    pub hauler_capacity: f32,
+    pub max_haulers_per_colony: usize,
+    pub logistics_interval: u64,
+    pub colony_stock_target: f32,
+    pub min_transport_amount: f32,
+    pub colony_founders: usize,
+    pub human_base_lifespan: u64,
+    pub population_interval: u64,
+    pub reproduction_ice_cost: f32,
+    pub reproduction_ice_reserve: f32,
+    pub mutation_rate: f64,
+    pub mutation_strength: f32,
+    pub expansion_interval: u64,
+    pub colony_founding_iron: f32,
+    pub colony_founding_ice: f32,
+    pub colony_settlers: usize,
+    pub colony_founding_range: f32,
+    pub colony_min_spacing: f32,
+    pub colony_site_radius: f32,
+    pub colony_initial_hulls: f32,
+    pub colony_settler_hulls: f32,
+    pub ice_upkeep_per_human: f32,
+    pub upgrade_mining_bonus: f32,
+    /// Ticks between state checksums, 0 disables them.
+    pub checksum_interval: u64,
+    /// Size in cells of the features of the density noise, 0 disables it.
+    pub density_noise_scale: f32,
+    pub density_noise_octaves: u32,
+    /// Density noise below this value leaves empty voids, must be below the noise median of 0.5.
+    pub density_void_threshold: f64,
+    /// Size in cells of the iron and gold rich regions, 0 disables them.
+    pub resource_noise_scale: f32,
+    /// How strongly the resource noise skews the local resource mix.
+    pub resource_noise_contrast: f64,
+    /// Grid spacing of potential asteroid cluster centers, clusters must fit within it.
+    pub cluster_spacing: f32,
+    /// Chance for a grid cell to hold a cluster, 0 disables clusters.
+    pub cluster_chance: f64,
+    pub cluster_min_radius: f32,
+    pub cluster_max_radius: f32,
+    pub cluster_density: f64,
+    /// Grid spacing of potential stars with belts, belts must fit within it.
+    pub belt_spacing: f32,
+    /// Chance for a grid cell to hold a star with a belt, 0 disables belts.
+    pub belt_chance: f64,
+    pub belt_min_radius: f32,
+    pub belt_max_radius: f32,
+    pub belt_width: f32,
+    pub belt_density: f64,
+    /// Whether asteroids mix up to three resources, otherwise each is made of its primary one.
+    pub mixed_compositions: bool,
+    pub recipes: Vec<Recipe>,
 }
 
 impl Default for SimulationSettings {
@@ -19,6 +87,61 @@ impl Default for SimulationSettings {
             max_asteroid_resource_amount: 1000.0,
             max_asteroid_scale: 10.0,
             asteroid_density: 0.00025,
+            scout_speed: 1.5,
+            scout_sensor_radius: 6.0,
+            scout_range: 600.0,
+            scout_launch_interval: 180,
+            max_scouts_per_colony: 4,
+            miner_speed: 1.0,
+            miner_range: 400.0,
+            miner_capacity: 50.0,
+            mining_rate: 0.5,
+            miner_launch_interval: 240,
+            max_miners_per_colony: 3,
+            depot_distance: 150.0,
+            hauler_speed: 1.5,
+            hauler_capacity: 100.0,
+            max_haulers_per_colony: 2,
+            logistics_interval: 60,
+            colony_stock_target: 200.0,
+            min_transport_amount: 10.0,
+            colony_founders: 12,
+            human_base_lifespan: 36_000,
+            population_interval: 60,
+            reproduction_ice_cost: 20.0,
+            reproduction_ice_reserve: 200.0,
+            mutation_rate: 0.05,
+            mutation_strength: 0.1,
+            expansion_interval: 600,
+            colony_founding_iron: 150.0,
+            colony_founding_ice: 150.0,
+            colony_settlers: 6,
+            colony_founding_range: 500.0,
+            colony_min_spacing: 200.0,
+            colony_site_radius: 60.0,
+            colony_initial_hulls: 8.0,
+            colony_settler_hulls: 3.0,
+            ice_upkeep_per_human: 0.0005,
+            upgrade_mining_bonus: 0.05,
+            checksum_interval: 600,
+            density_noise_scale: 800.0,
+            density_noise_octaves: 4,
+            density_void_threshold: 0.4,
+            resource_noise_scale: 1200.0,
+            resource_noise_contrast: 3.0,
+            cluster_spacing: 700.0,
+            cluster_chance: 0.4,
+            cluster_min_radius: 40.0,
+            cluster_max_radius: 160.0,
+            cluster_density: 0.003,
+            belt_spacing: 5000.0,
+            belt_chance: 0.5,
+            belt_min_radius: 800.0,
+            belt_max_radius: 2400.0,
+            belt_width: 120.0,
+            belt_density: 0.002,
+            mixed_compositions: true,
+            recipes: Recipe::default_recipes(),
         }
     }
 }
@@ -31,4 +154,264 @@ impl SimulationSettings {
     pub fn interval_fps(&self) -> Duration {
         Duration::from_secs_f64(1.0 / self.fps as f64)
     }
+
+    pub fn visible_update_cooldown_frames(&self) -> u32 {
+        (self.visible_update_cooldown.as_secs_f64() * self.fps as f64).ceil() as u32
+    }
+
+    pub fn clusters(&self) -> ClusterField {
+        ClusterField {
+            spacing: self.cluster_spacing,
+            chance: self.cluster_chance,
+            min_radius: self.cluster_min_radius,
+            max_radius: self.cluster_max_radius,
+            density: self.cluster_density,
+        }
+    }
+
+    pub fn belts(&self) -> BeltField {
+        BeltField {
+            spacing: self.belt_spacing,
+            chance: self.belt_chance,
+            min_radius: self.belt_min_radius,
+            max_radius: self.belt_max_radius,
+            width: self.belt_width,
+            density: self.belt_density,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), SettingsError> {
+        if self.tps == 0 {
+            return Err(SettingsError::ZeroTps);
+        }
+        if self.fps == 0 {
+            return Err(SettingsError::ZeroFps);
+        }
+        if !(0.0..=1.0).contains(&self.asteroid_density) {
+            return Err(SettingsError::DensityOutOfRange(self.asteroid_density));
+        }
+        if self.max_asteroid_scale.is_nan() || self.max_asteroid_scale <= 0.0 {
+            return Err(SettingsError::NotPositive("max_asteroid_scale"));
+        }
+        if !(0.0..0.5).contains(&self.density_void_threshold) {
+            return Err(SettingsError::VoidThresholdOutOfRange(
+                self.density_void_threshold,
+            ));
+        }
+        if !(self.density_noise_scale >= 0.0 && self.resource_noise_scale >= 0.0) {
+            return Err(SettingsError::NegativeNoiseScale);
+        }
+        let cluster_finite = [
+            self.cluster_spacing,
+            self.cluster_min_radius,
+            self.cluster_max_radius,
+        ]
+        .iter()
+        .all(|value| value.is_finite())
+            && self.cluster_chance.is_finite()
+            && self.cluster_density.is_finite();
+        if !(cluster_finite
+            && self.cluster_min_radius > 0.0
+            && self.cluster_min_radius <= self.cluster_max_radius
+            && self.cluster_max_radius <= self.cluster_spacing)
+        {
+            return Err(SettingsError::InvalidStructureSize("cluster"));
+        }
+        let belt_finite = [
+            self.belt_spacing,
+            self.belt_min_radius,
+            self.belt_max_radius,
+            self.belt_width,
+        ]
+        .iter()
+        .all(|value| value.is_finite())
+            && self.belt_chance.is_finite()
+            && self.belt_density.is_finite();
+        if !(belt_finite
+            && self.belt_width > 0.0
+            && self.belt_min_radius > 0.0
+            && self.belt_min_radius <= self.belt_max_radius
+            && self.belt_max_radius + self.belt_width * 0.5 <= self.belt_spacing)
+        {
+            return Err(SettingsError::InvalidStructureSize("belt"));
+        }
+        if self.max_asteroid_resource_amount.is_nan() || self.max_asteroid_resource_amount <= 0.0 {
+            return Err(SettingsError::NotPositive("max_asteroid_resource_amount"));
+        }
+
+        // Ships stall at zero speed and NaN poisons their positions.
+        for (name, value) in [
+            ("scout_speed", self.scout_speed),
+            ("scout_sensor_radius", self.scout_sensor_radius),
+            ("scout_range", self.scout_range),
+            ("miner_speed", self.miner_speed),
+            ("miner_range", self.miner_range),
+            ("miner_capacity", self.miner_capacity),
+            ("mining_rate", self.mining_rate),
+            ("depot_distance", self.depot_distance),
+            ("hauler_speed", self.hauler_speed),
+            ("hauler_capacity", self.hauler_capacity),
+            ("colony_founding_range", self.colony_founding_range),
+            ("colony_site_radius", self.colony_site_radius),
+        ] {
+            if !(value.is_finite() && value > 0.0) {
+                return Err(SettingsError::NotPositive(name));
+            }
+        }
+        for (name, value) in [
+            ("colony_stock_target", self.colony_stock_target),
+            ("min_transport_amount", self.min_transport_amount),
+            ("reproduction_ice_cost", self.reproduction_ice_cost),
+            ("reproduction_ice_reserve", self.reproduction_ice_reserve),
+            ("mutation_strength", self.mutation_strength),
+            ("colony_founding_iron", self.colony_founding_iron),
+            ("colony_founding_ice", self.colony_founding_ice),
+            ("colony_min_spacing", self.colony_min_spacing),
+            ("colony_initial_hulls", self.colony_initial_hulls),
+            ("colony_settler_hulls", self.colony_settler_hulls),
+            ("ice_upkeep_per_human", self.ice_upkeep_per_human),
+            ("upgrade_mining_bonus", self.upgrade_mining_bonus),
+        ] {
+            if !(value.is_finite() && value >= 0.0) {
+                return Err(SettingsError::Negative(name));
+            }
+        }
+        for (name, value) in [
+            ("colony_founders", self.colony_founders as u64),
+            ("colony_settlers", self.colony_settlers as u64),
+            ("human_base_lifespan", self.human_base_lifespan),
+        ] {
+            if value == 0 {
+                return Err(SettingsError::ZeroCount(name));
+            }
+        }
+        if !(0.0..=1.0).contains(&self.mutation_rate) {
+            return Err(SettingsError::NotProbability("mutation_rate"));
+        }
+        for recipe in &self.recipes {
+            let valid = recipe
+                .inputs
+                .iter()
+                .all(|(_, amount)| amount.is_finite() && *amount >= 0.0)
+                && recipe.output_amount.is_finite()
+                && recipe.output_amount > 0.0
+                && recipe.max_stock.is_finite()
+                && recipe.max_stock >= 0.0;
+            if !valid {
+                return Err(SettingsError::InvalidRecipe(recipe.name.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingsError {
+    ZeroTps,
+    ZeroFps,
+    DensityOutOfRange(f64),
+    VoidThresholdOutOfRange(f64),
+    NegativeNoiseScale,
+    InvalidStructureSize(&'static str),
+    NotPositive(&'static str),
+    Negative(&'static str),
+    ZeroCount(&'static str),
+    NotProbability(&'static str),
+    InvalidRecipe(String),
+}
+
+impl Display for SettingsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SettingsError::ZeroTps => write!(f, "tps must be greater than 0"),
+            SettingsError::ZeroFps => write!(f, "fps must be greater than 0"),
+            SettingsError::DensityOutOfRange(density) => {
+                write!(f, "Asteroid density {density} is outside of [0, 1]")
+            }
+            SettingsError::VoidThresholdOutOfRange(threshold) => {
+                write!(f, "Void threshold {threshold} is outside of [0, 0.5)")
+            }
+            SettingsError::NegativeNoiseScale => write!(f, "Noise scales can't be negative"),
+            SettingsError::InvalidStructureSize(kind) => write!(
+                f,
+                "The {kind} settings must be finite, the radius range positive and within the spacing"
+            ),
+            SettingsError::NotPositive(name) => write!(f, "{name} must be greater than 0"),
+            SettingsError::Negative(name) => write!(f, "{name} must be a non-negative number"),
+            SettingsError::ZeroCount(name) => write!(f, "{name} must be at least 1"),
+            SettingsError::NotProbability(name) => write!(f, "{name} must be within [0, 1]"),
+            SettingsError::InvalidRecipe(name) => write!(
+                f,
+                "Recipe {name} needs non-negative inputs and stock and a positive output"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_are_valid() {
+        assert_eq!(SimulationSettings::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn non_finite_speed_is_not_positive() {
+        let settings = SimulationSettings {
+            hauler_speed: f32::NAN,
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.validate(),
+            Err(SettingsError::NotPositive("hauler_speed"))
+        );
+    }
+
+    #[test]
+    fn negative_amount_is_rejected() {
+        let settings = SimulationSettings {
+            colony_founding_ice: -1.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.validate(),
+            Err(SettingsError::Negative("colony_founding_ice"))
+        );
+    }
+
+    #[test]
+    fn zero_count_is_rejected() {
+        let settings = SimulationSettings {
+            colony_founders: 0,
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.validate(),
+            Err(SettingsError::ZeroCount("colony_founders"))
+        );
+    }
+
+    #[test]
+    fn mutation_rate_must_be_a_probability() {
+        let settings = SimulationSettings {
+            mutation_rate: 1.5,
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.validate(),
+            Err(SettingsError::NotProbability("mutation_rate"))
+        );
+    }
+
+    #[test]
+    fn recipe_without_output_is_rejected() {
+        let mut settings = SimulationSettings::default();
+        settings.recipes[0].output_amount = 0.0;
+        let name = settings.recipes[0].name.clone();
+        assert_eq!(settings.validate(), Err(SettingsError::InvalidRecipe(name)));
+    }
 }
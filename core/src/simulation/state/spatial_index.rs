@@ -0,0 +1,138 @@
+use crate::math::point::Point;
+use crate::math::rect::Rect;
+use std::collections::{BTreeMap, BTreeSet};
+
+const CHUNK_SIZE: i64 = 64;
+
+/// Points bucketed into a grid of fixed-size chunks for area and nearest-neighbour queries.
+#[derive(Debug, Default, Clone)]
+pub struct SpatialIndex {
+    chunks: BTreeMap<Point<i64>, BTreeSet<Point<i64>>>,
+}
+
+impl SpatialIndex {
+    pub fn insert(&mut self, point: Point<i64>) -> bool {
+        self.chunks
+            .entry(chunk_of(point))
+            .or_default()
+            .insert(point)
+    }
+
+    pub fn remove(&mut self, point: Point<i64>) -> bool {
+        let chunk = chunk_of(point);
+        let Some(points) = self.chunks.get_mut(&chunk) else {
+            return false;
+        };
+
+        let removed = points.remove(&point);
+        if removed && points.is_empty() {
+            self.chunks.remove(&chunk);
+        }
+        removed
+    }
+
+    /// All points inside the rect, including its max edges.
+    pub fn query_rect(&self, rect: Rect<i64>) -> impl Iterator<Item = Point<i64>> + '_ {
+        let min = chunk_of(rect.min);
+        let max = chunk_of(rect.max);
+
+        self.chunks
+            .range(Point::new(min.x, i64::MIN)..=Point::new(max.x, i64::MAX))
+            .filter(move |(chunk, _)| (min.y..=max.y).contains(&chunk.y))
+            .flat_map(|(_, points)| points.iter().copied())
+            .filter(move |point| {
+                (rect.min.x..=rect.max.x).contains(&point.x)
+                    && (rect.min.y..=rect.max.y).contains(&point.y)
+            })
+    }
+
+    /// All points within `radius` of `center`.
+    pub fn query_radius(
+        &self,
+        center: Point<f32>,
+        radius: f32,
+    ) -> impl Iterator<Item = Point<i64>> + '_ {
+        let rect = Rect::new(
+            Point::new(center.x - radius, center.y - radius)
+                .floor()
+                .to_i64(),
+            Point::new(center.x + radius, center.y + radius)
+                .floor()
+                .to_i64(),
+        );
+
+        self.query_rect(rect)
+            .filter(move |point| center.distance(point.to_f32()) <= radius)
+    }
+
+    /// The closest point within `range` of `origin` accepted by `filter`.
+    /// Ties are broken by point order, so the result doesn't depend on chunk layout.
+    pub fn nearest(
+        &self,
+        origin: Point<f32>,
+        range: f32,
+        filter: impl Fn(Point<i64>) -> bool,
+    ) -> Option<Point<i64>> {
+        let closer =
+            |a: &(Point<i64>, f32), b: &(Point<i64>, f32)| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0));
+
+        let max_ring = (range / CHUNK_SIZE as f32).ceil() + 1.0;
+        let ring_chunks = (2.0 * max_ring + 1.0).powi(2);
+        if ring_chunks >= self.chunks.len() as f32 {
+            return self
+                .chunks
+                .values()
+                .flatten()
+                .filter(|point| filter(**point))
+                .map(|point| (*point, origin.distance(point.to_f32())))
+                .filter(|(_, distance)| *distance <= range)
+                .min_by(closer)
+                .map(|(point, _)| point);
+        }
+
+        let center = chunk_of(origin.floor().to_i64());
+        let mut best: Option<(Point<i64>, f32)> = None;
+        for ring in 0..=max_ring as i64 {
+            // Every point in this ring is at least this far away from the origin.
+            let ring_distance = ((ring - 1).max(0) * CHUNK_SIZE) as f32;
+            if ring_distance > range || best.is_some_and(|(_, d)| d <= ring_distance) {
+                break;
+            }
+
+            let candidates = ring_offsets(ring)
+                .filter_map(|offset| self.chunks.get(&(center + offset)))
+                .flatten()
+                .filter(|point| filter(**point))
+                .map(|point| (*point, origin.distance(point.to_f32())))
+                .filter(|(_, distance)| *distance <= range);
+            best = best.into_iter().chain(candidates).min_by(closer);
+        }
+        best.map(|(point, _)| point)
+    }
+}
+
+fn chunk_of(point: Point<i64>) -> Point<i64> {
+    Point::new(
+        point.x.div_euclid(CHUNK_SIZE),
+        point.y.div_euclid(CHUNK_SIZE),
+    )
+}
+
+/// Chunk offsets whose chebyshev distance from the center chunk is exactly `ring`.
+fn ring_offsets(ring: i64) -> impl Iterator<Item = Point<i64>> {
+    (-ring..=ring).flat_map(move |x| {
+        (-ring..=ring)
+            .filter(move |y| x.abs() == ring || y.abs() == ring)
+            .map(move |y| Point::new(x, y))
+    })
+}
+
+impl FromIterator<Point<i64>> for SpatialIndex {
+    fn from_iter<T: IntoIterator<Item = Point<i64>>>(iter: T) -> Self {
+        let mut index = Self::default();
+        for point in iter {
+            index.insert(point);
+        }
+        index
+    }
+}
@@ -1,7 +1,12 @@
 use crate::math::area::Area;
+use crate::math::point::Point;
 use crate::math::rect::Rect;
 use crate::math::size::Size;
+use crate::simulation::layer::Layer;
+use crate::simulation::state::settings::SimulationSettings;
+use std::path::PathBuf;
 
+#[derive(Debug, Clone)]
 pub enum SimCommand {
     Clear,
     Pause,
@@ -10,5 +15,32 @@ pub enum SimCommand {
     Shutdown,
     SetVisibleRect(Rect<f32>),
     SetScreenSize(Size<u32>),
+    SetLayerVisibility(Layer, bool),
     ScoutArea(Area<f32>),
+    FoundColony(Point<i64>),
+    Save(PathBuf),
+    Load(PathBuf),
+    /// Multiplier on the configured tps, clamped to 0.01..=1000, `f32::INFINITY` runs unbounded.
+    SetTimeScale(f32),
+    /// Advances the given number of ticks as fast as possible, then pauses.
+    StepTicks(u64),
+    /// Runs as fast as possible until the given tick, then pauses.
+    RunUntilTick(u64),
+    UpdateSettings(Box<SimulationSettings>),
+    StartRecording,
+    StopRecording(PathBuf),
+}
+
+impl SimCommand {
+    /// Whether the command changes the simulation state and therefore belongs in a replay.
+    /// View, pause and file commands are left out, the recorded tick already captures pausing.
+    pub fn is_replayable(&self) -> bool {
+        matches!(
+            self,
+            SimCommand::Clear
+                | SimCommand::ScoutArea(_)
+                | SimCommand::FoundColony(_)
+                | SimCommand::UpdateSettings(_)
+        )
+    }
 }
@@ -1,2 +1,55 @@
-#[derive(Debug, Clone, Copy)]
-pub enum SimEvent {}
+use crate::math::point::Point;
+use crate::simulation::state::human::HumanId;
+use crate::simulation::state::resource::ResourceType;
+use crate::simulation::state::ship::{ShipId, ShipKind};
+
+#[derive(Debug, Clone)]
+pub enum SimEvent {
+    AsteroidDiscovered(Point<i64>),
+    AsteroidDepleted(Point<i64>),
+    ColonyFounded(Point<i64>),
+    DepotEstablished(Point<i64>),
+    ShipLaunched {
+        id: ShipId,
+        kind: ShipKind,
+        from: Point<i64>,
+    },
+    ShipArrived {
+        id: ShipId,
+        kind: ShipKind,
+        at: Point<i64>,
+    },
+    ShipLost {
+        id: ShipId,
+        kind: ShipKind,
+        at: Point<i64>,
+    },
+    HumanBorn {
+        id: HumanId,
+        colony: Point<i64>,
+    },
+    HumanDied(HumanId),
+    /// A colony ran short of ice for its upkeep after being fed.
+    ColonyStarving {
+        colony: Point<i64>,
+        shortage: f32,
+    },
+    ResourcesDelivered {
+        colony: Point<i64>,
+        resource: ResourceType,
+        amount: f32,
+    },
+    PauseChanged(bool),
+    TimeScaleChanged(f32),
+    TickReached(u64),
+    SettingsChanged,
+    SettingsRejected(String),
+    StateSaved,
+    StateLoaded,
+    SaveFailed(String),
+    LoadFailed(String),
+    RecordingStarted,
+    RecordingSaved,
+    RecordingFailed(String),
+    ReplayFinished,
+}
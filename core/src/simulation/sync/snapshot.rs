@@ -1,10 +1,36 @@
+use crate::math::point::Point;
+use crate::simulation::layer::LayerSet;
+use crate::simulation::state::colony::Colony;
+use crate::simulation::state::human::{Gene, Human, LineageId};
+use crate::simulation::state::production::{Product, ProductionRates};
+use crate::simulation::state::resource::{ResourceRegistry, ResourceType};
 use crate::simulation::state::settings::SimulationSettings;
+use std::collections::HashMap;
 use std::time::Duration;
 
 #[derive(Default, Clone)]
 pub struct SimSnapshot {
     pub discovered_asteroids: usize,
+    pub explored_cells: u64,
+    pub scouts: usize,
+    pub miners: usize,
+    pub haulers: usize,
+    pub population: usize,
+    pub colonies: Vec<ColonySnapshot>,
+    pub depots: usize,
+    pub transport_requests: usize,
     pub settings: SimulationSettings,
+    pub resource_types: ResourceRegistry,
+    pub checksum: Option<StateChecksum>,
+    pub recording: bool,
+    pub paused: bool,
+    pub time_scale: f32,
+    pub run_until: Option<u64>,
+    pub visible_layers: LayerSet,
+    /// Layers that had something drawn into them for this frame.
+    pub drawn_layers: LayerSet,
+    /// Ticks per second actually achieved, measured over wall-clock time.
+    pub effective_tps: f64,
     pub avg_frame: Duration,
     pub avg_tick: Duration,
 }
@@ -14,3 +40,80 @@ impl SimSnapshot {
         Duration::from_secs_f64(self.avg_frame.as_secs_f64() * self.settings.fps as f64)
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateChecksum {
+    pub tick: u64,
+    pub value: u64,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GeneStats {
+    pub mean: f32,
+    pub min: f32,
+    pub max: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ColonySnapshot {
+    pub position: Point<i64>,
+    pub population: usize,
+    pub max_generation: u32,
+    pub resources: Vec<(ResourceType, f32)>,
+    /// Total worth of the stockpiled resources.
+    pub resource_value: f32,
+    pub products: Vec<(Product, f32)>,
+    pub production: ProductionRates,
+    pub genes: [(Gene, GeneStats); Gene::ALL.len()],
+    /// Lineages by member count, most common first.
+    pub lineages: Vec<(LineageId, usize)>,
+}
+
+impl ColonySnapshot {
+    pub fn new<'a>(
+        position: Point<i64>,
+        colony: &Colony,
+        members: impl Iterator<Item = &'a Human>,
+        resource_types: &ResourceRegistry,
+    ) -> Self {
+        let members: Vec<_> = members.collect();
+
+        let genes = Gene::ALL.map(|gene| {
+            let values = members.iter().map(|human| human.genome.get(gene));
+            let stats = if members.is_empty() {
+                GeneStats::default()
+            } else {
+                GeneStats {
+                    mean: values.clone().sum::<f32>() / members.len() as f32,
+                    min: values.clone().fold(f32::MAX, f32::min),
+                    max: values.fold(f32::MIN, f32::max),
+                }
+            };
+            (gene, stats)
+        });
+
+        let mut lineage_counts: HashMap<LineageId, usize> = HashMap::new();
+        for human in &members {
+            *lineage_counts.entry(human.lineage).or_default() += 1;
+        }
+        let mut lineages: Vec<_> = lineage_counts.into_iter().collect();
+        lineages.sort_by(|(a_id, a), (b_id, b)| b.cmp(a).then(a_id.cmp(b_id)));
+
+        Self {
+            position,
+            population: members.len(),
+            max_generation: members.iter().map(|h| h.generation).max().unwrap_or(0),
+            resources: resource_types
+                .types()
+                .map(|resource| (resource, colony.resources.total(resource)))
+                .collect(),
+            resource_value: resource_types.value_of(&colony.resources),
+            products: [Product::Hull, Product::Upgrade]
+                .map(|product| (product, colony.product(product)))
+                .to_vec(),
+            production: colony.production.clone(),
+            genes,
+            lineages,
+        }
+    }
+}
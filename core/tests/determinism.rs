@@ -0,0 +1,20 @@
+use pss_core::math::point::Point;
+use pss_core::simulation::state::SimState;
+use pss_core::simulation::Simulation;
+
+fn checksum_after(seed: u64, ticks: u64) -> u64 {
+    let state = SimState::new_with_seed(seed).with_colony(Point::new(1000, 700));
+    let mut simulation = Simulation::new(state);
+    simulation.step(ticks);
+    simulation.state().checksum()
+}
+
+#[test]
+fn same_seed_gives_same_checksum() {
+    assert_eq!(checksum_after(2, 2000), checksum_after(2, 2000));
+}
+
+#[test]
+fn different_seed_gives_different_checksum() {
+    assert_ne!(checksum_after(2, 2000), checksum_after(3, 2000));
+}
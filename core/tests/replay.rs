@@ -0,0 +1,51 @@
+use pss_core::math::area::Area;
+use pss_core::math::circle::Circle;
+use pss_core::math::point::Point;
+use pss_core::simulation::replay::Replay;
+use pss_core::simulation::state::SimState;
+use pss_core::simulation::sync::command::SimCommand;
+use pss_core::simulation::sync::event::SimEvent;
+use pss_core::simulation::Simulation;
+
+#[test]
+fn replay_reproduces_recorded_session() {
+    let path = std::env::temp_dir().join(format!("pss-replay-test-{}.pss", std::process::id()));
+
+    let state = SimState::new_with_seed(2).with_colony(Point::new(1000, 700));
+    let mut simulation = Simulation::new(state);
+    simulation.step(100);
+    simulation.handle_command(SimCommand::StartRecording);
+    simulation.step(200);
+    simulation.handle_command(SimCommand::ScoutArea(Area::Circle(Circle::new(
+        Point::new(1200.0, 800.0),
+        80.0,
+    ))));
+    // Not replayable, so it must not affect the outcome either way.
+    simulation.handle_command(SimCommand::SetTimeScale(2.0));
+    simulation.step(300);
+    simulation.handle_command(SimCommand::FoundColony(Point::new(1400, 500)));
+    simulation.step(500);
+    simulation.handle_command(SimCommand::StopRecording(path.clone()));
+
+    let mut saved = false;
+    while let Some(event) = simulation.poll_event() {
+        saved |= matches!(event, SimEvent::RecordingSaved);
+    }
+    assert!(saved);
+
+    let replay = Replay::load_from(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(replay.commands.len(), 2);
+
+    let (state, mut schedule) = replay.into_parts().unwrap();
+    assert_eq!(state.tick(), 100);
+    let mut replayed = Simulation::new(state);
+    while replayed.state().tick() < simulation.state().tick() {
+        schedule.apply_due(&mut replayed);
+        replayed.step(1);
+    }
+
+    assert!(schedule.is_empty());
+    assert_eq!(replayed.state().checksum(), simulation.state().checksum());
+    assert_eq!(replayed.state().colonies.len(), 2);
+}
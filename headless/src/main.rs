@@ -0,0 +1,283 @@
+use pss_core::math::point::Point;
+use pss_core::simulation::replay::{CommandSchedule, Replay};
+use pss_core::simulation::state::resource::ResourceRegistry;
+use pss_core::simulation::state::SimState;
+use pss_core::simulation::sync::event::SimEvent;
+use pss_core::simulation::sync::snapshot::SimSnapshot;
+use pss_core::simulation::Simulation;
+use std::error::Error;
+use std::num::NonZeroU64;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "\
+Usage: pss-headless [OPTIONS]
+
+Options:
+  --seed <SEED>          World seed (default: 2)
+  --colony <X,Y>         Position of the initial colony (default: 1000,700)
+  --resources <PATH>     Resource type definitions for a new world
+  --load <PATH>          Start from a saved state instead of a new world
+  --replay <PATH>        Start from a recording and feed its commands back in
+  --ticks <N>            Number of ticks to run
+  --duration <SECONDS>   Wall-clock time to run for
+  --report-every <N>     Print a progress line every N ticks (N > 0)
+  --save <PATH>          Save the final state to a file
+  -h, --help             Print this help";
+
+#[derive(Debug)]
+struct Args {
+    seed: u64,
+    colony: Point<i64>,
+    resources: Option<PathBuf>,
+    load: Option<PathBuf>,
+    replay: Option<PathBuf>,
+    ticks: Option<u64>,
+    duration: Option<Duration>,
+    report_every: Option<NonZeroU64>,
+    save: Option<PathBuf>,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Self {
+            seed: 2,
+            colony: Point::new(1000, 700),
+            resources: None,
+            load: None,
+            replay: None,
+            ticks: None,
+            duration: None,
+            report_every: None,
+            save: None,
+        }
+    }
+}
+
+impl Args {
+    fn parse() -> Result<Option<Self>, String> {
+        let mut args = Args::default();
+        let mut iter = std::env::args().skip(1);
+
+        while let Some(arg) = iter.next() {
+            if arg == "-h" || arg == "--help" {
+                return Ok(None);
+            }
+
+            let value = iter
+                .next()
+                .ok_or_else(|| format!("Missing value for '{arg}'"))?;
+            let invalid = || format!("Invalid value '{value}' for '{arg}'");
+
+            match arg.as_str() {
+                "--seed" => args.seed = value.parse().map_err(|_| invalid())?,
+                "--colony" => args.colony = parse_point(&value).ok_or_else(invalid)?,
+                "--resources" => args.resources = Some(PathBuf::from(&value)),
+                "--load" => args.load = Some(PathBuf::from(&value)),
+                "--replay" => args.replay = Some(PathBuf::from(&value)),
+                "--ticks" => args.ticks = Some(value.parse().map_err(|_| invalid())?),
+                "--duration" => {
+                    let secs: f64 = value.parse().map_err(|_| invalid())?;
+                    args.duration = Some(Duration::try_from_secs_f64(secs).map_err(|_| invalid())?);
+                }
+                "--report-every" => args.report_every = Some(value.parse().map_err(|_| invalid())?),
+                "--save" => args.save = Some(PathBuf::from(&value)),
+                _ => return Err(format!("Unknown option '{arg}'")),
+            }
+        }
+
+        if args.load.is_some() && args.replay.is_some() {
+            return Err("--load and --replay can't be combined".to_string());
+        }
+        if args.resources.is_some() && (args.load.is_some() || args.replay.is_some()) {
+            return Err("--resources only applies to new worlds".to_string());
+        }
+        if args.ticks.is_none() && args.duration.is_none() {
+            return Err("Either --ticks or --duration is required".to_string());
+        }
+        Ok(Some(args))
+    }
+}
+
+fn parse_point(value: &str) -> Option<Point<i64>> {
+    let (x, y) = value.split_once(',')?;
+    Some(Point::new(x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
+#[derive(Debug, Default)]
+struct EventCounts {
+    asteroids_discovered: usize,
+    asteroids_depleted: usize,
+    colonies_founded: usize,
+    depots_established: usize,
+    ships_launched: usize,
+    ships_lost: usize,
+    humans_born: usize,
+    humans_died: usize,
+    colonies_starving: usize,
+    resources_delivered: f32,
+}
+
+impl EventCounts {
+    fn record(&mut self, event: &SimEvent) {
+        match event {
+            SimEvent::AsteroidDiscovered(_) => self.asteroids_discovered += 1,
+            SimEvent::AsteroidDepleted(_) => self.asteroids_depleted += 1,
+            SimEvent::ColonyFounded(_) => self.colonies_founded += 1,
+            SimEvent::DepotEstablished(_) => self.depots_established += 1,
+            SimEvent::ShipLaunched { .. } => self.ships_launched += 1,
+            SimEvent::ShipLost { .. } => self.ships_lost += 1,
+            SimEvent::HumanBorn { .. } => self.humans_born += 1,
+            SimEvent::HumanDied(_) => self.humans_died += 1,
+            SimEvent::ColonyStarving { .. } => self.colonies_starving += 1,
+            SimEvent::ResourcesDelivered { amount, .. } => self.resources_delivered += amount,
+            _ => {}
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse() {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("{err}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let (state, mut schedule) = match (&args.load, &args.replay) {
+        (Some(path), _) => (SimState::load_from(path)?, CommandSchedule::default()),
+        (_, Some(path)) => Replay::load_from(path)?.into_parts()?,
+        _ => {
+            let resource_types = match &args.resources {
+                Some(path) => ResourceRegistry::load_from(path)?,
+                None => ResourceRegistry::default(),
+            };
+            let state = SimState::new_with_seed(args.seed)
+                .with_resource_types(resource_types)
+                .with_colony(args.colony);
+            (state, CommandSchedule::default())
+        }
+    };
+    let mut simulation = Simulation::new(state);
+    let mut events = EventCounts::default();
+
+    let start = Instant::now();
+    let mut ticks = 0;
+    loop {
+        if args.ticks.is_some_and(|max| ticks >= max)
+            || args.duration.is_some_and(|max| start.elapsed() >= max)
+        {
+            break;
+        }
+
+        if schedule.apply_due(&mut simulation) {
+            println!("Replay finished at tick {}", simulation.state().tick());
+        }
+        simulation.step(1);
+        ticks += 1;
+
+        while let Some(event) = simulation.poll_event() {
+            events.record(&event);
+        }
+
+        if args
+            .report_every
+            .is_some_and(|every| ticks % every.get() == 0)
+        {
+            report_progress(&simulation, start.elapsed());
+        }
+    }
+    let elapsed = start.elapsed();
+
+    let mut snapshot = SimSnapshot::default();
+    simulation.update_snapshot(&mut snapshot);
+    if ticks > 0 {
+        snapshot.avg_tick = Duration::from_secs_f64(elapsed.as_secs_f64() / ticks as f64);
+    }
+    print_summary(&simulation, &snapshot, &events, ticks, elapsed);
+
+    if let Some(path) = &args.save {
+        simulation.state().save_to(path)?;
+        println!(
+            "Saved state at tick {} to {}",
+            simulation.state().tick(),
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+fn report_progress(simulation: &Simulation, elapsed: Duration) {
+    let state = simulation.state();
+    println!(
+        "[{:>8.2}s] tick {:>9}  colonies {:>4}  population {:>6}  asteroids {:>7}  checksum {:016x}",
+        elapsed.as_secs_f64(),
+        state.tick(),
+        state.colonies.len(),
+        state.population(),
+        state.discovered_asteroids.len(),
+        state.checksum(),
+    );
+}
+
+fn print_summary(
+    simulation: &Simulation,
+    snapshot: &SimSnapshot,
+    events: &EventCounts,
+    ticks: u64,
+    elapsed: Duration,
+) {
+    let state = simulation.state();
+    let ticks_per_sec = ticks as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+
+    println!("seed                  {}", state.seed());
+    println!("final tick            {}", state.tick());
+    println!("checksum              {:016x}", state.checksum());
+    println!("ticks run             {ticks}");
+    println!("elapsed               {:.3}s", elapsed.as_secs_f64());
+    println!("ticks/s               {ticks_per_sec:.1}");
+    println!("avg tick              {:.3?}", snapshot.avg_tick);
+    println!();
+    println!("colonies              {}", snapshot.colonies.len());
+    println!("population            {}", snapshot.population);
+    println!("discovered asteroids  {}", snapshot.discovered_asteroids);
+    println!("explored cells        {}", snapshot.explored_cells);
+    println!("scouts                {}", snapshot.scouts);
+    println!("miners                {}", snapshot.miners);
+    println!("haulers               {}", snapshot.haulers);
+    println!("depots                {}", snapshot.depots);
+    println!("transport requests    {}", snapshot.transport_requests);
+    println!();
+    println!("events                {events:#?}");
+
+    for colony in &snapshot.colonies {
+        println!();
+        println!(
+            "colony {:?}  population {}  max generation {}  resource value {:.1}",
+            colony.position, colony.population, colony.max_generation, colony.resource_value
+        );
+        for (resource, amount) in &colony.resources {
+            let name = snapshot.resource_types.name(*resource);
+            println!("  {name}: {amount:.1}");
+        }
+        for (product, amount) in &colony.products {
+            println!("  {product}: {amount:.1}");
+        }
+    }
+}